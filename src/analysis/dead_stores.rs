@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::lexer::Span;
+use crate::parser::{Expression, Program, Statement};
+
+/// Find writes whose value is overwritten before it is ever read (dead stores)
+///
+/// Returns the variable name and the span of each dead write, in source order.
+pub fn find_dead_stores(program: &Program) -> Vec<(String, Span)> {
+    // Last write to each variable that has not been read yet
+    let mut unread_writes: HashMap<&str, Span> = HashMap::new();
+    let mut dead_stores = Vec::new();
+
+    for statement in &program.statements {
+        match statement {
+            Statement::Declaration { name, value, span, .. }
+            | Statement::Assignment { name, value, span } => {
+                // The right-hand side is evaluated before the write happens
+                mark_reads(value, &mut unread_writes);
+                if let Some(previous) = unread_writes.insert(name, *span) {
+                    dead_stores.push((name.clone(), previous));
+                }
+            }
+            Statement::Print { value, arguments, .. } => {
+                mark_reads(value, &mut unread_writes);
+                for argument in arguments {
+                    mark_reads(argument, &mut unread_writes);
+                }
+            }
+            Statement::PrintError { value, .. } => mark_reads(value, &mut unread_writes),
+            Statement::Table { start, end, .. } => {
+                mark_reads(start, &mut unread_writes);
+                mark_reads(end, &mut unread_writes);
+            }
+            Statement::Avtem { .. } | Statement::Comment { .. } => {}
+        }
+    }
+
+    dead_stores.sort_by_key(|(_, span)| span.start);
+    dead_stores
+}
+
+/// Mark every variable read by an expression as used
+fn mark_reads(expr: &Expression, unread_writes: &mut HashMap<&str, Span>) {
+    match expr {
+        Expression::Variable { name, .. } => {
+            unread_writes.remove(name.as_str());
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            mark_reads(left, unread_writes);
+            mark_reads(right, unread_writes);
+        }
+        Expression::Grouped { inner, .. } => mark_reads(inner, unread_writes),
+        Expression::FunctionCall { arguments, .. } => {
+            for argument in arguments {
+                mark_reads(argument, unread_writes);
+            }
+        }
+        Expression::ListLiteral { elements, .. } => {
+            for element in elements {
+                mark_reads(element, unread_writes);
+            }
+        }
+        Expression::NumberLiteral { .. }
+        | Expression::StringLiteral { .. }
+        | Expression::BooleanLiteral { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::Parser;
+
+    fn dead_stores(input: &str) -> Vec<(String, usize)> {
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        find_dead_stores(&program)
+            .into_iter()
+            .map(|(name, span)| (name, span.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_dead_store() {
+        let stores = dead_stores("DECLARA X EST 1\nX EST 2\nX EST 3\nSCRIBE(X)");
+        assert_eq!(stores, vec![("X".to_string(), 1), ("X".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_read_between_writes() {
+        let stores = dead_stores("DECLARA X EST 1\nSCRIBE(X)\nX EST X ADDIUS 1\nSCRIBE(X)");
+        assert!(stores.is_empty());
+    }
+
+    #[test]
+    fn test_self_referencing_write_is_a_read() {
+        let stores = dead_stores("DECLARA X EST 1\nX EST X ADDIUS 1");
+        assert!(stores.is_empty());
+    }
+}
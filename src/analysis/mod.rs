@@ -0,0 +1,7 @@
+mod dead_stores;
+mod node_at;
+mod numerals;
+
+pub use dead_stores::find_dead_stores;
+pub use node_at::{node_at, SpanKind};
+pub use numerals::{find_invalid_numerals, find_non_canonical_numerals, fix_non_canonical_numerals};
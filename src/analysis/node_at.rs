@@ -0,0 +1,97 @@
+use crate::parser::{Expression, Program, Statement};
+
+/// The AST node found at a cursor position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanKind<'a> {
+    Statement(&'a Statement),
+    Expression(&'a Expression),
+}
+
+/// Find the innermost node whose span contains the byte offset `pos`: an
+/// expression if the cursor is on one, otherwise the enclosing statement
+/// (for editor hover and go-to-definition)
+pub fn node_at(program: &Program, pos: usize) -> Option<SpanKind<'_>> {
+    let statement = program.statements.iter().find(|s| s.span().contains(pos))?;
+
+    let expressions: Vec<&Expression> = match statement {
+        Statement::Declaration { value, .. }
+        | Statement::Assignment { value, .. }
+        | Statement::PrintError { value, .. } => vec![value],
+        Statement::Print { value, arguments, .. } => std::iter::once(value).chain(arguments).collect(),
+        Statement::Table { start, end, .. } => vec![start, end],
+        Statement::Avtem { .. } | Statement::Comment { .. } => Vec::new(),
+    };
+
+    match expressions.into_iter().find_map(|e| expression_at(e, pos)) {
+        Some(expression) => Some(SpanKind::Expression(expression)),
+        None => Some(SpanKind::Statement(statement)),
+    }
+}
+
+/// The innermost sub-expression of `expr` containing `pos`, if any
+fn expression_at(expr: &Expression, pos: usize) -> Option<&Expression> {
+    if !expr.span().contains(pos) {
+        return None;
+    }
+
+    let inner = match expr {
+        Expression::BinaryOp { left, right, .. } => {
+            expression_at(left, pos).or_else(|| expression_at(right, pos))
+        }
+        Expression::Grouped { inner, .. } => expression_at(inner, pos),
+        Expression::FunctionCall { arguments, .. } => arguments.iter().find_map(|a| expression_at(a, pos)),
+        Expression::ListLiteral { elements, .. } => elements.iter().find_map(|e| expression_at(e, pos)),
+        Expression::NumberLiteral { .. }
+        | Expression::StringLiteral { .. }
+        | Expression::BooleanLiteral { .. }
+        | Expression::Variable { .. } => None,
+    };
+
+    inner.or(Some(expr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{Lexer, Span};
+    use crate::parser::{BinaryOperator, Parser};
+
+    fn parse(input: &str) -> Program {
+        Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap()
+    }
+
+    #[test]
+    fn test_node_at_variable_reference() {
+        let source = "DECLARA SUMMA EST XV\nSCRIBE(\"{}\", SUMMA ADDIUS II)";
+        let program = parse(source);
+        let cursor = source.rfind("SUMMA").unwrap() + 2;
+
+        match node_at(&program, cursor) {
+            Some(SpanKind::Expression(Expression::Variable { name, span })) => {
+                assert_eq!(name, "SUMMA");
+                assert_eq!(span.len(), 5);
+            }
+            other => panic!("expected the SUMMA reference, got {:?}", other),
+        }
+
+        // On the operator keyword the innermost node is the whole binary operation
+        let cursor = source.find("ADDIUS").unwrap();
+        assert!(matches!(
+            node_at(&program, cursor),
+            Some(SpanKind::Expression(Expression::BinaryOp { operator: BinaryOperator::Add, .. }))
+        ));
+
+        // On a keyword outside any expression, the statement itself
+        assert!(matches!(node_at(&program, 1), Some(SpanKind::Statement(Statement::Declaration { .. }))));
+        assert_eq!(node_at(&program, source.len() + 10), None);
+    }
+
+    #[test]
+    fn test_span_contains() {
+        let span = Span::new(4, 9, 1, 5);
+        assert!(span.contains(4) && span.contains(8));
+        assert!(!span.contains(3) && !span.contains(9));
+        assert_eq!(span.len(), 5);
+        assert!(Span::new(4, 4, 1, 5).is_empty());
+    }
+}
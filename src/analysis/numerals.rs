@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use crate::error::NumerusError;
+use crate::lexer::{Lexer, Span, Token, TokenKind};
+use crate::roman::{from_roman, from_roman_lenient, looks_like_roman, RomanError};
+
+/// Find Roman literals written in a non-canonical form (accepted in lenient mode)
+///
+/// Returns the literal as written, its canonical spelling, and its span.
+pub fn find_non_canonical_numerals(tokens: &[Token]) -> Vec<(String, String, Span)> {
+    tokens
+        .iter()
+        .filter_map(|token| {
+            token
+                .canonical_form()
+                .map(|canonical| (token.lexeme.clone(), canonical, token.span))
+        })
+        .collect()
+}
+
+/// Find names made only of Roman letters that the lexer rejected as numerals
+/// (e.g. `VX`) and so read as variables, with the rule each one broke
+///
+/// Declared names are skipped: `DECLARA VX EST V` is a legitimate variable.
+pub fn find_invalid_numerals(tokens: &[Token], lenient: bool) -> Vec<(String, RomanError, Span)> {
+    let declared: HashSet<&str> = tokens
+        .windows(2)
+        .filter(|pair| pair[0].kind == TokenKind::Declara)
+        .filter_map(|pair| match &pair[1].kind {
+            TokenKind::Identifier(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    tokens
+        .iter()
+        .filter_map(|token| match &token.kind {
+            TokenKind::Identifier(name)
+                if name.len() >= 2 && looks_like_roman(name) && !declared.contains(name.as_str()) =>
+            {
+                let parsed = if lenient { from_roman_lenient(name) } else { from_roman(name) };
+                parsed.err().map(|reason| (name.clone(), reason, token.span))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Rewrite every non-canonical Roman literal in `source` to its canonical form
+/// (e.g. `IIII` -> `IV`), leaving everything else untouched
+pub fn fix_non_canonical_numerals(source: &str) -> Result<String, NumerusError> {
+    let mut lexer = Lexer::new(source);
+    lexer.set_lenient_numerals(true);
+    let tokens = lexer.tokenize()?;
+
+    let mut fixed = source.to_string();
+    // Replace from the end so earlier byte offsets stay valid
+    for (_, canonical, span) in find_non_canonical_numerals(&tokens).into_iter().rev() {
+        fixed.replace_range(span.start..span.end, &canonical);
+    }
+
+    Ok(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    fn non_canonical(input: &str) -> Vec<(String, String)> {
+        let mut lexer = Lexer::new(input);
+        lexer.set_lenient_numerals(true);
+        let tokens = lexer.tokenize().unwrap();
+        find_non_canonical_numerals(&tokens)
+            .into_iter()
+            .map(|(found, canonical, _)| (found, canonical))
+            .collect()
+    }
+
+    #[test]
+    fn test_non_canonical_numeral_warns() {
+        assert_eq!(
+            non_canonical("DECLARA X EST IIII"),
+            vec![("IIII".to_string(), "IV".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_canonical_numeral_does_not_warn() {
+        assert!(non_canonical("DECLARA X EST IV").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_numeral_reports_broken_rule() {
+        let tokens = Lexer::new("DECLARA X EST VX\nDECLARA IIIII EST XV\nSCRIBE(IIIII ADDIUS MIM)").tokenize().unwrap();
+        let found: Vec<_> = find_invalid_numerals(&tokens, false)
+            .into_iter()
+            .map(|(name, reason, span)| (name, reason, span.line))
+            .collect();
+        assert_eq!(found, vec![
+            ("VX".to_string(), RomanError::InvalidSubtractive("VX".to_string()), 1),
+            ("MIM".to_string(), RomanError::InvalidSubtractive("MIM".to_string()), 3),
+        ]);
+    }
+
+    #[test]
+    fn test_fix_non_canonical_numerals() {
+        let source = "DECLARA X EST IIII\nDECLARA Y EST XXXX ADDIUS XLII\nSCRIBE(\"IIII\")\n";
+        assert_eq!(
+            fix_non_canonical_numerals(source).unwrap(),
+            "DECLARA X EST IV\nDECLARA Y EST XL ADDIUS XLII\nSCRIBE(\"IIII\")\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_leaves_canonical_source_untouched() {
+        let source = "DECLARA X EST MCMXCIX\nSCRIBE(X)\n";
+        assert_eq!(fix_non_canonical_numerals(source).unwrap(), source);
+    }
+}
@@ -68,15 +68,50 @@ pub fn print_help() {
     println!("{}", "║ OUTPUT (SCRIBE):                                          ║".bright_yellow());
     println!("{} {:<w$} {}", b, "SCRIBE(X)             - Imprime (numeri in Romanis)".white(), b);
     println!("{} {:<w$} {}", b, "SCRIBE(\"Salve!\")      - Imprime string".white(), b);
+    println!("{} {:<w$} {}", b, "SCRIBE(\"X = {}\", X)   - Imprime cum formula".white(), b);
+    println!("{} {:<w$} {}", b, "SCRIBE_ERRATUM(X)     - Imprime in stderr".white(), b);
+    println!("{} {:<w$} {}", b, "MONSTRA_TABULAM(1, XX)".white(), b);
+    println!("{} {:<w$} {}", b, "                      - Imprime tabulam Arabicam/Romanam".white(), b);
     println!("{}", "║                                                           ║".bright_yellow());
     println!("{}", "║ FUNCTIONES (Functions):                                   ║".bright_yellow());
     println!("{} {:<w$} {}", b, "ROMANIZA(42)          - Converte ad Roman string".cyan(), b);
+    println!("{} {:<w$} {}", b, "ROMANIZA_MINUSCULA(42)".cyan(), b);
+    println!("{} {:<w$} {}", b, "                      - Minusculis: xlii".cyan(), b);
     println!("{} {:<w$} {}", b, "ARABIZA(XLII)         - Converte ad Arabic string".cyan(), b);
+    println!("{} {:<w$} {}", b, "EXPRIME(\"XLII\")       - Lege numerum ex string".cyan(), b);
+    println!("{} {:<w$} {}", b, "COMPARA(A, B)         - Compara: -1, 0 vel 1".cyan(), b);
+    println!("{} {:<w$} {}", b, "PRIMUS(XCVII)         - VERUM si numerus primus est".cyan(), b);
+    println!("{} {:<w$} {}", b, "SORTA({III, II})      - Ordina copiam (VERUM: descendens)".cyan(), b);
+    println!("{} {:<w$} {}", b, "ADDE(L, XX)           - Nova copia cum XX addito".cyan(), b);
+    println!("{} {:<w$} {}", b, "REMOVE(L, II)         - Nova copia sine elemento II".cyan(), b);
+    println!("{} {:<w$} {}", b, "EX_AMBITU(\"HOME\")     - Variabilis ambitus (CHORDA)".cyan(), b);
+    println!("{} {:<w$} {}", b, "HEX(\"FF\")             - Numerus hexadecimalis: CCLV".cyan(), b);
+    println!("{} {:<w$} {}", b, "VERTE(\"ROMA\")         - Inverte: \"AMOR\"".cyan(), b);
+    println!("{} {:<w$} {}", b, "DIVIDE_CUM_RESIDUO(VII, II)".cyan(), b);
+    println!("{} {:<w$} {}", b, "                      - Quotus et residuum: {III, I}".cyan(), b);
+    println!("{} {:<w$} {}", b, "DUPLICA(XXI)          - Duplum: XLII".cyan(), b);
+    println!("{} {:<w$} {}", b, "DIMIDIUM(XLV)         - Dimidium: XXII".cyan(), b);
+    println!("{} {:<w$} {}", b, "SUMMA_USQUE(XX)       - I + II + ... + XX: CCX".cyan(), b);
+    println!("{} {:<w$} {}", b, "SUMMA_INTER(XV, XX)   - XV + XVI + ... + XX: CV".cyan(), b);
+    println!("{} {:<w$} {}", b, "INSPICE(A ADDIUS II)  - Imprime valorem, reddit eum".cyan(), b);
+    println!("{} {:<w$} {}", b, "TYPUS(A)              - Nomen typi (NUMERUS, CHORDA...)".cyan(), b);
+    println!("{} {:<w$} {}", b, "PAR(X) / IMPAR(X)     - VERUM si numerus par / impar est".cyan(), b);
+    println!("{} {:<w$} {}", b, "UNCIA(VI)             - Nomen fractionis: SEMIS".cyan(), b);
+    println!("{} {:<w$} {}", b, "PROGRESSIO(1, XX, II) - Copia: {I, III, ..., XIX}".cyan(), b);
+    println!("{} {:<w$} {}", b, "FACTORES(CCCLX)       - Factores primi: {II, II, ...}".cyan(), b);
     println!("{}", "║                                                           ║".bright_yellow());
     println!("{}", "║ CEREMONIALE:                                              ║".bright_yellow());
     println!("{} {:<w$} {}", b, "AVTEM                 - Ceremoniale no-op".magenta(), b);
+    println!("{} {:<w$} {}", b, "AVTEM(\"nota\")         - No-op cum nota (non imprimitur)".magenta(), b);
     println!("{} {:<w$} {}", b, "NOTA: commentarius    - Commentarius (ignoratur)".magenta(), b);
     println!("{}", "║                                                           ║".bright_yellow());
+    println!("{}", "║ MANDATA REPL (REPL commands):                             ║".bright_yellow());
+    println!("{} {:<w$} {}", b, "ARBOR [codex]         - Monstra arborem syntaxis".magenta(), b);
+    println!("{} {:<w$} {}", b, "ITERUM                - Exsequere lineam priorem iterum".magenta(), b);
+    println!("{} {:<w$} {}", b, "HISTORIA              - Monstra lineas priores".magenta(), b);
+    println!("{} {:<w$} {}", b, "DEBUGA                - Exsequere sententias gradatim".magenta(), b);
+    println!("{} {:<w$} {}", b, "PERGE                 - Exi e modo gradatim".magenta(), b);
+    println!("{}", "║                                                           ║".bright_yellow());
     println!("{}", "║ EXEMPLUM:                                                 ║".bright_yellow());
     println!("{} {:<w$} {}", b, "DECLARA A EST XV".green(), b);
     println!("{} {:<w$} {}", b, "DECLARA B EST 10".green(), b);
@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::NumerusError;
+use crate::roman::NumeralMode;
+
+/// Name of the preferences file looked for in the current directory, then the home directory
+pub const CONFIG_FILE: &str = ".numerusrc";
+
+/// Persistent preferences read from a `.numerusrc` file of `key = value` lines
+///
+/// ```text
+/// # comments and blank lines are ignored
+/// numerals = arabic     # roman (default) or arabic
+/// lenient = on          # accept additive numerals such as IIII
+/// color = off
+/// prompt = "> "
+/// ```
+///
+/// These are only defaults: command-line flags and `NUMERUS_PROMPT` take precedence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub numeral_mode: NumeralMode,
+    pub lenient: bool,
+    pub color: bool,
+    pub prompt: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            numeral_mode: NumeralMode::Roman,
+            lenient: false,
+            color: true,
+            prompt: None,
+        }
+    }
+}
+
+impl Config {
+    /// Parse the contents of a `.numerusrc` file
+    pub fn parse(text: &str) -> Result<Self, NumerusError> {
+        let mut config = Config::default();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |reason: String| NumerusError::InvalidConfig { line: index + 1, reason };
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("'{}' non est 'clavis = valor'", line)))?;
+            let (key, value) = (key.trim(), unquote(value.trim()));
+
+            match key {
+                "numerals" => {
+                    config.numeral_mode = match value {
+                        "roman" => NumeralMode::Roman,
+                        "arabic" => NumeralMode::ArabicOnly,
+                        _ => return Err(invalid(format!("'{}' expectat roman vel arabic, non '{}'", key, value))),
+                    }
+                }
+                "lenient" | "color" => {
+                    let flag = parse_switch(value)
+                        .ok_or_else(|| invalid(format!("'{}' expectat on vel off, non '{}'", key, value)))?;
+                    if key == "lenient" {
+                        config.lenient = flag;
+                    } else {
+                        config.color = flag;
+                    }
+                }
+                "prompt" => config.prompt = Some(value.to_string()),
+                _ => return Err(invalid(format!("clavis '{}' ignota est", key))),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Read the first `.numerusrc` found in `dirs`, or the defaults if there is none
+    pub fn load_from(dirs: &[PathBuf]) -> Result<Self, NumerusError> {
+        match dirs.iter().map(|dir| dir.join(CONFIG_FILE)).find(|path| path.is_file()) {
+            Some(path) => Self::read(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Read `.numerusrc` from the current directory, falling back to the home directory
+    pub fn load() -> Result<Self, NumerusError> {
+        let dirs: Vec<PathBuf> = std::env::current_dir()
+            .ok()
+            .into_iter()
+            .chain(std::env::var_os("HOME").map(PathBuf::from))
+            .collect();
+        Self::load_from(&dirs)
+    }
+
+    fn read(path: &Path) -> Result<Self, NumerusError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| NumerusError::ConfigUnreadable { path: path.display().to_string(), reason: e.to_string() })?;
+        Self::parse(&text)
+    }
+}
+
+/// Drop a `#` comment, unless the `#` is inside a quoted value
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Remove surrounding double quotes, which keep leading or trailing spaces in a value
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" | "true" => Some(true),
+        "off" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sample_rc() {
+        let config = Config::parse(
+            "# preferences\n\nnumerals = arabic\nlenient=on   # IIII too\ncolor = off\nprompt = \"# \"\n",
+        )
+        .unwrap();
+        assert_eq!(config, Config {
+            numeral_mode: NumeralMode::ArabicOnly,
+            lenient: true,
+            color: false,
+            prompt: Some("# ".to_string()),
+        });
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        assert!(matches!(
+            Config::parse("color = off\nlingua = latina"),
+            Err(NumerusError::InvalidConfig { line: 2, .. })
+        ));
+        assert!(matches!(Config::parse("lenient = maybe"), Err(NumerusError::InvalidConfig { line: 1, .. })));
+        assert!(matches!(Config::parse("numerals"), Err(NumerusError::InvalidConfig { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_load_prefers_first_directory() {
+        let root = std::env::temp_dir().join(format!("numerusrc-{}", std::process::id()));
+        let (local, home) = (root.join("local"), root.join("home"));
+        std::fs::create_dir_all(&local).unwrap();
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(home.join(CONFIG_FILE), "lenient = on").unwrap();
+
+        let dirs = [local.clone(), home.clone()];
+        assert!(Config::load_from(&dirs).unwrap().lenient);
+
+        std::fs::write(local.join(CONFIG_FILE), "color = off").unwrap();
+        let config = Config::load_from(&dirs).unwrap();
+        assert!(!config.color);
+        assert!(!config.lenient);
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(Config::load_from(&dirs).unwrap(), Config::default());
+    }
+}
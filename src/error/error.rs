@@ -1,5 +1,6 @@
 use thiserror::Error;
 use crate::lexer::Span;
+use crate::roman::RomanError;
 
 /// All error types for Numerus++ with Latin-style messages
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -26,9 +27,45 @@ pub enum NumerusError {
         line: usize,
     },
 
-    #[error("ERRATUM LEXICUM: Numerus {value} extra fines est! (I-MMMCMXCIX solum)")]
-    NumberOutOfRange {
-        value: i64,
+    #[error("ERRATUM LEXICUM: Sequentia effugii '{sequence}' invalida in linea {line}, columna {column}!")]
+    InvalidEscape {
+        sequence: String,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("ERRATUM LEXICUM: Forma numeri '{found}' non sustinetur in linea {line}, columna {column}! Solum numeri integri (42) et Romani (XLII) admittuntur.")]
+    UnsupportedNumberFormat {
+        found: String,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("ERRATUM LEXICUM: Nomen nimis longum in linea {line}, columna {column} (maximum {max} litterae)!")]
+    IdentifierTooLong {
+        max: usize,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("ERRATUM LEXICUM: Numerus fractus '{found}' in linea {line}, columna {column}! Numerus++ solum numeros integros habet; Romani fractiones per uncias scribebant.")]
+    FractionalNotSupported {
+        found: String,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("ERRATUM LEXICUM: Numerus {digits} cifrarum extra fines est! ({range})")]
+    TooManyDigits {
+        digits: usize,
+        /// The literals allowed in the lexer's numeral mode
+        range: &'static str,
+        span: Span,
+    },
+
+    #[error("ERRATUM LEXICUM: '{lexeme}' litteras Romanas et numeros Arabicos miscet! Scribe aut Romane aut Arabice.")]
+    MixedNumeralForm {
+        lexeme: String,
         span: Span,
     },
 
@@ -54,14 +91,28 @@ pub enum NumerusError {
         opening_span: Span,
     },
 
-    #[error("ERRATUM SYNTAXIS: Finis inexpectatus programmatis!")]
-    UnexpectedEndOfInput,
+    #[error("ERRATUM SYNTAXIS: Programma abrupte finitum! Expectabatur '{expected}'.")]
+    UnexpectedEndOfInput {
+        expected: String,
+        span: Span,
+    },
 
     #[error("ERRATUM SYNTAXIS: Identificator expectatus!")]
     ExpectedIdentifier {
         span: Span,
     },
 
+    #[error("ERRATUM SYNTAXIS: EST sine nomine variabilis! Scribe 'NOMEN EST valor'.")]
+    MissingVariableName {
+        span: Span,
+    },
+
+    #[error("ERRATUM SYNTAXIS: Operator '{operator}' sine operando sinistro!")]
+    MissingLeftOperand {
+        operator: String,
+        span: Span,
+    },
+
     // ═══════════════════════════════════════════════════════════
     // RUNTIME ERRORS (ERRATA TEMPORIS EXECUTIONIS)
     // ═══════════════════════════════════════════════════════════
@@ -71,11 +122,23 @@ pub enum NumerusError {
         name: String,
     },
 
+    #[error("ERRATUM: Variable '{name}' assignari non potest antequam declarata est! Scribe 'DECLARA {name} EST ...' primum.")]
+    AssignBeforeDeclare {
+        name: String,
+        span: Span,
+    },
+
     #[error("ERRATUM: Variable '{name}' iam declarata est!")]
     VariableAlreadyDeclared {
         name: String,
     },
 
+    #[error("ERRATUM: '{name}' constans est et mutari non potest!")]
+    AssignToConstant {
+        name: String,
+        span: Option<Span>,
+    },
+
     #[error("ERRATUM: Divisio per nihilum prohibita est! (Etiam Romani hoc sciebant)")]
     DivisionByZero {
         span: Span,
@@ -84,16 +147,35 @@ pub enum NumerusError {
     #[error("ERRATUM: Numerus negativus {value} in Romanis exprimi non potest!")]
     NegativeRomanConversion {
         value: i32,
+        span: Option<Span>,
     },
 
-    #[error("ERRATUM: Numerus {value} nimis magnus pro Romanis (maximum MMMCMXCIX)!")]
-    RomanOverflow {
-        value: i32,
+    /// Raised both for Arabic literals above the limit and for values too
+    /// large to convert, so the two read the same
+    #[error("ERRATUM: Numerus {value} extra fines est! (1–3999 / I–MMMCMXCIX)")]
+    NumberOutOfRange {
+        value: i64,
+        span: Option<Span>,
+    },
+
+    #[error("{reason}")]
+    InvalidRomanForm {
+        reason: RomanError,
+        span: Option<Span>,
     },
 
     #[error("ERRATUM: Numerus {value} nimis magnus vel parvus!")]
     IntegerOverflow {
         value: i64,
+        span: Option<Span>,
+    },
+
+    #[error("ERRATUM: HEX(\"{digits}\") extra fines est! ({range})")]
+    HexOutOfRange {
+        digits: String,
+        /// The numbers allowed in the interpreter's numeral mode
+        range: &'static str,
+        span: Span,
     },
 
     #[error("ERRATUM: Operatio '{operation}' requirit {expected}!")]
@@ -103,15 +185,90 @@ pub enum NumerusError {
         span: Span,
     },
 
+    #[error("ERRATUM: Variable '{name}' typi {expected} est, sed valor {found} datus est!")]
+    TypeAnnotationMismatch {
+        name: String,
+        expected: String,
+        found: String,
+        span: Span,
+    },
+
+    #[error("ERRATUM: Formula SCRIBE {placeholders} argumenta requirit, sed {arguments} data sunt!")]
+    FormatArgumentMismatch {
+        placeholders: usize,
+        arguments: usize,
+        span: Span,
+    },
+
+    #[error("ERRATUM: Forma '{specifier}' ignota est! Utere ':a' (Arabice) vel ':r' (Romane).")]
+    InvalidFormatSpecifier {
+        specifier: String,
+        span: Span,
+    },
+
+    #[error("ERRATUM: String non potest repeti {count} vicibus (numerus negativus)!")]
+    NegativeRepetition {
+        count: i32,
+        span: Span,
+    },
+
+    #[error("ERRATUM: String longitudinis {len} nimis longa est (maximum {max})!")]
+    StringTooLong {
+        len: usize,
+        max: usize,
+    },
+
+    #[error("ERRATUM: Copia {len} elementorum nimis longa est (maximum {max})!")]
+    ListTooLong {
+        len: usize,
+        max: usize,
+    },
+
+    #[error("ERRATUM: Limes {limit} passuum excessus est! Programma nimis longum.")]
+    StepLimitExceeded {
+        limit: usize,
+    },
+
     // ═══════════════════════════════════════════════════════════
     // BUILT-IN FUNCTION ERRORS
     // ═══════════════════════════════════════════════════════════
 
+    #[error("ERRATUM: Functio '{name}' {expected} argumenta expectat, sed {found} accepit!")]
+    WrongArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+
     #[error("ERRATUM: Functio '{name}' argumentum invalidum accepit!")]
     InvalidFunctionArgument {
         name: String,
         span: Span,
     },
+
+    #[error("ERRATUM: Index {index} extra copiam {len} elementorum est (I-{len})!")]
+    IndexOutOfBounds {
+        index: i32,
+        len: usize,
+        span: Span,
+    },
+
+    // ═══════════════════════════════════════════════════════════
+    // CONFIGURATION ERRORS (.numerusrc)
+    // ═══════════════════════════════════════════════════════════
+
+    #[error("ERRATUM: .numerusrc linea {line}: {reason}!")]
+    InvalidConfig {
+        line: usize,
+        reason: String,
+    },
+
+    #[error("ERRATUM: Non possum legere '{path}': {reason}")]
+    ConfigUnreadable {
+        path: String,
+        reason: String,
+    },
 }
 
 impl NumerusError {
@@ -119,36 +276,259 @@ impl NumerusError {
     pub fn span(&self) -> Option<Span> {
         match self {
             Self::InvalidRomanNumeral { span, .. } => Some(*span),
-            Self::NumberOutOfRange { span, .. } => Some(*span),
+            Self::MixedNumeralForm { span, .. } => Some(*span),
+            Self::TooManyDigits { span, .. } => Some(*span),
             Self::UnexpectedToken { span, .. } => Some(*span),
+            Self::UnexpectedEndOfInput { span, .. } => Some(*span),
             Self::ExpectedExpression { span, .. } => Some(*span),
             Self::UnclosedParenthesis { opening_span } => Some(*opening_span),
             Self::ExpectedIdentifier { span } => Some(*span),
+            Self::MissingVariableName { span } => Some(*span),
+            Self::MissingLeftOperand { span, .. } => Some(*span),
+            Self::AssignBeforeDeclare { span, .. } => Some(*span),
             Self::DivisionByZero { span } => Some(*span),
+            Self::NegativeRomanConversion { span, .. } => *span,
+            Self::NumberOutOfRange { span, .. } => *span,
+            Self::IntegerOverflow { span, .. } => *span,
+            Self::HexOutOfRange { span, .. } => Some(*span),
+            Self::AssignToConstant { span, .. } => *span,
+            Self::InvalidRomanForm { span, .. } => *span,
             Self::TypeMismatch { span, .. } => Some(*span),
+            Self::TypeAnnotationMismatch { span, .. } => Some(*span),
+            Self::FormatArgumentMismatch { span, .. } => Some(*span),
+            Self::InvalidFormatSpecifier { span, .. } => Some(*span),
+            Self::NegativeRepetition { span, .. } => Some(*span),
+            Self::WrongArgumentCount { span, .. } => Some(*span),
             Self::InvalidFunctionArgument { span, .. } => Some(*span),
+            Self::IndexOutOfBounds { span, .. } => Some(*span),
             _ => None,
         }
     }
+
+    /// Attach a span to an error raised without one (e.g. while converting a
+    /// value for output), leaving errors that already carry a span untouched
+    pub fn with_span(self, at: Span) -> Self {
+        match self {
+            Self::NegativeRomanConversion { value, span: None } => {
+                Self::NegativeRomanConversion { value, span: Some(at) }
+            }
+            Self::NumberOutOfRange { value, span: None } => {
+                Self::NumberOutOfRange { value, span: Some(at) }
+            }
+            Self::InvalidRomanForm { reason, span: None } => {
+                Self::InvalidRomanForm { reason, span: Some(at) }
+            }
+            Self::IntegerOverflow { value, span: None } => {
+                Self::IntegerOverflow { value, span: Some(at) }
+            }
+            Self::AssignToConstant { name, span: None } => {
+                Self::AssignToConstant { name, span: Some(at) }
+            }
+            other => other,
+        }
+    }
+}
+
+impl From<RomanError> for NumerusError {
+    /// Keep the specific reason a conversion failed; spans are attached
+    /// afterwards with [`NumerusError::with_span`]
+    fn from(err: RomanError) -> Self {
+        match err {
+            RomanError::NegativeOrZero(value) => Self::NegativeRomanConversion { value, span: None },
+            RomanError::Overflow(value) => Self::NumberOutOfRange { value: value.into(), span: None },
+            reason => Self::InvalidRomanForm { reason, span: None },
+        }
+    }
 }
 
 /// Format an error with source context for pretty printing
 pub fn format_error_with_context(source: &str, error: &NumerusError) -> String {
     let mut output = format!("{}\n", error);
 
-    if let Some(span) = error.span() {
-        if let Some(line) = source.lines().nth(span.line.saturating_sub(1)) {
-            output.push_str(&format!(
-                "  --> linea {}:{}\n   |\n {:>3} | {}\n   | {}{}\n",
-                span.line,
-                span.column,
-                span.line,
-                line,
-                " ".repeat(span.column.saturating_sub(1)),
-                "^".repeat((span.end - span.start).max(1))
-            ));
-        }
+    if let Some(span) = error.span().or_else(|| location_span(source, error))
+        && let Some(line) = source.lines().nth(span.line.saturating_sub(1))
+    {
+        // Keep the line's own tabs in the padding so the caret lines up at any tab width
+        let padding: String = line
+            .chars()
+            .take(span.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        output.push_str(&format!(
+            "  --> linea {}:{}\n   |\n {:>3} | {}\n   | {:<width$}{}\n",
+            span.line,
+            span.column,
+            span.line,
+            line,
+            padding,
+            "^".repeat((span.end - span.start).max(1)),
+            width = span.column.saturating_sub(1)
+        ));
+
     }
 
     output
 }
+
+/// Format an error raised while executing the statement at `statement` (see
+/// `Interpreter::failed_statement`): as [`format_error_with_context`], but a
+/// statement spanning several lines is also shown in full, joined onto one line
+pub fn format_error_in_statement(source: &str, error: &NumerusError, statement: Option<Span>) -> String {
+    let mut output = format_error_with_context(source, error);
+
+    // Only one line of a multi-line statement is shown above, so add the whole statement
+    if let Some(text) = statement.and_then(|span| source.get(span.start..span.end))
+        && text.contains('\n')
+    {
+        let text: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        output.push_str(&format!("   = in sententia: {}\n", text.join(" ")));
+    }
+
+    output
+}
+
+/// Build a span for errors that only record a line (and column), so they can
+/// be shown in context too; only its line, column and width are meaningful
+fn location_span(source: &str, error: &NumerusError) -> Option<Span> {
+    let (line, column, width) = match error {
+        NumerusError::UnexpectedCharacter { line, column, .. }
+        | NumerusError::IdentifierTooLong { line, column, .. } => (*line, *column, 1),
+        NumerusError::InvalidEscape { sequence: text, line, column }
+        | NumerusError::UnsupportedNumberFormat { found: text, line, column }
+        | NumerusError::FractionalNotSupported { found: text, line, column } => {
+            (*line, *column, text.chars().count())
+        }
+        NumerusError::UnterminatedString { line } => {
+            let text = source.lines().nth(line.saturating_sub(1))?;
+            (*line, unclosed_quote_column(text)?, 1)
+        }
+        _ => return None,
+    };
+
+    Some(Span::new(0, width, line, column))
+}
+
+/// Column (1-based) of the quote opening a string left unclosed at the end of `line`
+fn unclosed_quote_column(line: &str) -> Option<usize> {
+    let mut open = None;
+    let mut escaped = false;
+
+    for (i, ch) in line.chars().enumerate() {
+        match (open, ch) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(_), '"') => open = None,
+            (None, '"') => open = Some(i + 1),
+            _ => {}
+        }
+    }
+
+    open
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roman::{from_roman, to_roman};
+
+    #[test]
+    fn test_context_for_unexpected_character() {
+        let source = "DECLARA X EST XV\nSCRIBE(X @ II)";
+        let error = NumerusError::UnexpectedCharacter { ch: '@', line: 2, column: 10 };
+        let output = format_error_with_context(source, &error);
+        assert!(output.contains("  --> linea 2:10\n"), "{}", output);
+        assert!(output.contains("   2 | SCRIBE(X @ II)\n   |          ^\n"), "{}", output);
+    }
+
+    #[test]
+    fn test_context_caret_follows_tabs() {
+        let source = "DECLARA\tTOTAL\tEST\tXV @";
+        let error = NumerusError::UnexpectedCharacter { ch: '@', line: 1, column: 22 };
+        let output = format_error_with_context(source, &error);
+        assert!(output.ends_with(&format!("   | {}\t{}\t{}\t{}^\n",
+            " ".repeat(7), " ".repeat(5), " ".repeat(3), " ".repeat(3))), "{:?}", output);
+    }
+
+    #[test]
+    fn test_context_for_unterminated_string() {
+        let source = r#"SCRIBE("a" ADDIUS "b \" c)"#;
+        let error = NumerusError::UnterminatedString { line: 1 };
+        let output = format_error_with_context(source, &error);
+        assert!(output.contains("  --> linea 1:19\n"), "{}", output);
+        assert!(output.ends_with(&format!("   | {}^\n", " ".repeat(18))), "{}", output);
+    }
+
+    #[test]
+    fn test_context_shows_multi_line_statement() {
+        use crate::interpreter::Interpreter;
+        use crate::lexer::Lexer;
+        use crate::parser::Parser;
+        use crate::roman::NumeralMode;
+
+        let source = "DECLARA X EST XV\nDECLARA Y EST X ADDIUS\n    XX DIVIDE (X SUBTRAHE XV)\nSCRIBE(Y)";
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        let program = Parser::new(tokens).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.run(&program).unwrap_err();
+
+        let message = format_error_in_statement(source, &error, interpreter.failed_statement());
+        assert!(message.contains("linea 3"), "{}", message);
+        assert!(message.contains("   = in sententia: DECLARA Y EST X ADDIUS XX DIVIDE (X SUBTRAHE XV)\n"), "{}", message);
+        assert!(!format_error_with_context(source, &error).contains("in sententia"));
+
+        // The statement comes from the interpreter, so any numeral mode works
+        let source = "DECLARA TOTAL EST 4000 ADDIUS\n    -5 DIVIDE 0";
+        let mut lexer = Lexer::new(source);
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.run(&program).unwrap_err();
+        let message = format_error_in_statement(source, &error, interpreter.failed_statement());
+        assert!(message.contains("   = in sententia: DECLARA TOTAL EST 4000 ADDIUS -5 DIVIDE 0\n"), "{}", message);
+
+        // A statement on a single line is already shown in full
+        let source = "DECLARA Y EST XX DIVIDE 0";
+        let message = format_error_in_statement(
+            source,
+            &NumerusError::DivisionByZero { span: Span::new(14, 25, 1, 15) },
+            Some(Span::new(0, source.len(), 1, 1)),
+        );
+        assert!(!message.contains("in sententia"), "{}", message);
+    }
+
+    #[test]
+    fn test_from_roman_error_keeps_reason() {
+        let overflow = NumerusError::from(to_roman(4000).unwrap_err());
+        assert_eq!(overflow, NumerusError::NumberOutOfRange { value: 4000, span: None });
+
+        let repetition = NumerusError::from(from_roman("VV").unwrap_err());
+        assert_eq!(repetition, NumerusError::InvalidRomanForm {
+            reason: RomanError::InvalidRepetition('V'),
+            span: None,
+        });
+        assert_ne!(overflow, repetition);
+
+        let negative = NumerusError::from(to_roman(-3).unwrap_err());
+        assert_eq!(negative, NumerusError::NegativeRomanConversion { value: -3, span: None });
+    }
+
+    #[test]
+    fn test_roman_form_error_takes_span() {
+        let span = Span::new(0, 2, 1, 1);
+        let err = NumerusError::from(from_roman("IIII").unwrap_err()).with_span(span);
+        assert_eq!(err.span(), Some(span));
+        assert_eq!(err.to_string(), RomanError::TooManyRepetitions('I').to_string());
+    }
+
+    #[test]
+    fn test_out_of_range_message_names_bounds() {
+        let converted = NumerusError::from(to_roman(4000).unwrap_err());
+        assert_eq!(converted.to_string(), "ERRATUM: Numerus 4000 extra fines est! (1–3999 / I–MMMCMXCIX)");
+
+        let literal = crate::lexer::Lexer::new("SCRIBE(5000)").tokenize().unwrap_err();
+        assert_eq!(literal.to_string(), "ERRATUM: Numerus 5000 extra fines est! (1–3999 / I–MMMCMXCIX)");
+        assert_eq!(literal.span(), Some(Span::new(7, 11, 1, 8)));
+
+        assert_eq!(to_roman(4000).unwrap_err().to_string(), converted.to_string());
+    }
+}
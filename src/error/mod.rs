@@ -1,3 +1,3 @@
 mod error;
 
-pub use error::{NumerusError, format_error_with_context};
+pub use error::{NumerusError, format_error_in_statement, format_error_with_context};
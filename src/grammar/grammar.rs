@@ -0,0 +1,141 @@
+//! A machine-readable description of the language, generated from the
+//! token, operator and built-in definitions so external tools stay in sync
+
+use crate::lexer::keywords;
+use crate::parser::{BinaryOperator, BuiltinFunction};
+
+/// Marker that starts a comment running to the end of the line
+const COMMENT_MARKER: &str = "NOTA:";
+
+/// The language's keywords, operators and built-in functions
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrammarSpec {
+    pub keywords: Vec<&'static str>,
+    pub operators: Vec<OperatorSpec>,
+    pub builtins: Vec<BuiltinSpec>,
+    pub comment: &'static str,
+}
+
+/// A binary operator and how tightly it binds (higher binds tighter)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatorSpec {
+    pub name: &'static str,
+    pub precedence: u8,
+}
+
+/// A built-in function and how many arguments it accepts
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltinSpec {
+    pub name: &'static str,
+    pub min_arguments: usize,
+    pub max_arguments: usize,
+}
+
+/// Describe the grammar of Numerus++
+pub fn describe() -> GrammarSpec {
+    GrammarSpec {
+        // Reserved words that are neither operators nor built-in functions
+        keywords: keywords()
+            .iter()
+            .copied()
+            .filter(|name| !BinaryOperator::ALL.iter().any(|op| op.symbol() == *name))
+            .filter(|name| !BuiltinFunction::ALL.iter().any(|function| function.name() == *name))
+            .collect(),
+        operators: BinaryOperator::ALL
+            .iter()
+            .map(|op| OperatorSpec { name: op.symbol(), precedence: op.precedence() })
+            .collect(),
+        builtins: BuiltinFunction::ALL
+            .iter()
+            .map(|function| BuiltinSpec {
+                name: function.name(),
+                min_arguments: function.arity(),
+                max_arguments: function.max_arity(),
+            })
+            .collect(),
+        comment: COMMENT_MARKER,
+    }
+}
+
+impl GrammarSpec {
+    /// Render as a single-line JSON object
+    pub fn to_json(&self) -> String {
+        let keywords: Vec<String> = self.keywords.iter().map(|k| format!(r#""{}""#, k)).collect();
+        let operators: Vec<String> = self
+            .operators
+            .iter()
+            .map(|op| format!(r#"{{"name":"{}","precedence":{}}}"#, op.name, op.precedence))
+            .collect();
+        let builtins: Vec<String> = self
+            .builtins
+            .iter()
+            .map(|b| {
+                format!(
+                    r#"{{"name":"{}","min_arguments":{},"max_arguments":{}}}"#,
+                    b.name, b.min_arguments, b.max_arguments
+                )
+            })
+            .collect();
+
+        format!(
+            r#"{{"keywords":[{}],"operators":[{}],"builtins":[{}],"comment":"{}"}}"#,
+            keywords.join(","),
+            operators.join(","),
+            builtins.join(","),
+            self.comment
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_contains_core_names() {
+        let json = describe().to_json();
+        assert!(json.contains(r#""DECLARA""#));
+        assert!(json.contains(r#"{"name":"ADDIUS","precedence":1}"#));
+        assert!(json.contains(r#"{"name":"MULTIPLICA","precedence":2}"#));
+        assert!(json.contains(r#"{"name":"ROMANIZA","min_arguments":1,"max_arguments":1}"#));
+        assert!(json.contains(r#"{"name":"SORTA","min_arguments":1,"max_arguments":2}"#));
+    }
+
+    #[test]
+    fn test_every_reserved_word_is_described_once() {
+        let spec = describe();
+        let mut described: Vec<&str> = spec
+            .keywords
+            .iter()
+            .copied()
+            .chain(spec.operators.iter().map(|op| op.name))
+            .chain(spec.builtins.iter().map(|b| b.name))
+            .collect();
+        let mut reserved = keywords().to_vec();
+        described.sort_unstable();
+        reserved.sort_unstable();
+        assert_eq!(described, reserved);
+        assert!(spec.keywords.contains(&"SCRIBE_ERRATUM"));
+        assert!(!spec.keywords.contains(&"ADDIUS"));
+    }
+
+    #[test]
+    fn test_keywords_lex_as_keywords() {
+        use crate::lexer::{Lexer, TokenKind};
+
+        let spec = describe();
+        let names = spec
+            .keywords
+            .iter()
+            .chain(spec.operators.iter().map(|op| &op.name))
+            .chain(spec.builtins.iter().map(|b| &b.name));
+        for name in names {
+            let tokens = Lexer::new(name).tokenize().unwrap();
+            assert!(
+                !matches!(tokens[0].kind, TokenKind::Identifier(_)),
+                "{} is not lexed as a keyword",
+                name
+            );
+        }
+    }
+}
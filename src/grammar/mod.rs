@@ -0,0 +1,3 @@
+mod grammar;
+
+pub use grammar::{describe, BuiltinSpec, GrammarSpec, OperatorSpec};
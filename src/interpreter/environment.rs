@@ -1,12 +1,18 @@
 use std::collections::HashMap;
 use crate::error::NumerusError;
-use crate::roman::to_roman;
+use crate::parser::TypeAnnotation;
+use crate::roman::{to_roman, MAX_ROMAN};
 
-/// Runtime value - can be a number or a string
+/// Read-only names every program can use: the range Roman numerals can express
+const CONSTANTS: [(&str, i32); 2] = [("MAXIMUS", MAX_ROMAN), ("MINIMUS", 1)];
+
+/// Runtime value - a number, a string, a boolean or a list of values
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Number(i32),
     String(String),
+    Boolean(bool),
+    List(Vec<Value>),
 }
 
 impl Value {
@@ -16,17 +22,37 @@ impl Value {
             Value::String(s) => Ok(s.clone()),
             Value::Number(n) => {
                 // Convert to Roman
-                if *n <= 0 {
-                    return Err(NumerusError::NegativeRomanConversion { value: *n });
-                }
-                if *n > 3999 {
-                    return Err(NumerusError::RomanOverflow { value: *n });
-                }
-                to_roman(*n).map_err(|_| NumerusError::RomanOverflow { value: *n })
+                Ok(to_roman(*n)?)
+            }
+            Value::Boolean(b) => Ok(if *b { "VERUM" } else { "FALSUM" }.to_string()),
+            Value::List(items) => {
+                let items = items
+                    .iter()
+                    .map(Value::to_output_string)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(format!("{{{}}}", items.join(", ")))
             }
         }
     }
 
+    /// Name of this value's type, as written in type annotations
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "NUMERUS",
+            Value::String(_) => "CHORDA",
+            Value::Boolean(_) => "VERITAS",
+            Value::List(_) => "COPIA",
+        }
+    }
+
+    /// Check whether this value satisfies a declared type annotation
+    pub fn matches_type(&self, annotation: TypeAnnotation) -> bool {
+        matches!(
+            (self, annotation),
+            (Value::Number(_), TypeAnnotation::Numerus) | (Value::String(_), TypeAnnotation::Chorda)
+        )
+    }
+
     /// Check if this is a number
     pub fn is_number(&self) -> bool {
         matches!(self, Value::Number(_))
@@ -54,11 +80,34 @@ impl Value {
     }
 }
 
+/// Values of the same type are ordered (numbers numerically, strings
+/// lexicographically); values of different types are not comparable
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Number(n) => write!(f, "{}", n),
             Value::String(s) => write!(f, "{}", s),
+            Value::Boolean(b) => write!(f, "{}", if *b { "VERUM" } else { "FALSUM" }),
+            Value::List(items) => {
+                write!(f, "{{")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "}}")
+            }
         }
     }
 }
@@ -67,26 +116,54 @@ impl std::fmt::Display for Value {
 #[derive(Debug, Clone, Default)]
 pub struct Environment {
     variables: HashMap<String, Value>,
+    annotations: HashMap<String, TypeAnnotation>,
+    /// Variable names in declaration order, so iteration is deterministic
+    order: Vec<String>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
+            annotations: HashMap::new(),
+            order: Vec::new(),
         }
     }
 
+    /// Whether `name` is a built-in constant (`MAXIMUS`, `MINIMUS`), which
+    /// can be read but never declared or assigned
+    pub fn is_constant(name: &str) -> bool {
+        CONSTANTS.iter().any(|(constant, _)| *constant == name)
+    }
+
     /// Declare a new variable
     pub fn declare(&mut self, name: String, value: Value) -> Result<(), NumerusError> {
+        if Self::is_constant(&name) {
+            return Err(NumerusError::AssignToConstant { name, span: None });
+        }
         if self.variables.contains_key(&name) {
             return Err(NumerusError::VariableAlreadyDeclared { name });
         }
+        self.order.push(name.clone());
         self.variables.insert(name, value);
         Ok(())
     }
 
+    /// Bind a variable whether or not it already exists, replacing any previous
+    /// value and type annotation (for host-provided values)
+    pub fn define(&mut self, name: String, value: Value) {
+        self.annotations.remove(&name);
+        if !self.variables.contains_key(&name) {
+            self.order.push(name.clone());
+        }
+        self.variables.insert(name, value);
+    }
+
     /// Assign to an existing variable
     pub fn assign(&mut self, name: &str, value: Value) -> Result<(), NumerusError> {
+        if Self::is_constant(name) {
+            return Err(NumerusError::AssignToConstant { name: name.to_string(), span: None });
+        }
         if !self.variables.contains_key(name) {
             return Err(NumerusError::UndefinedVariable {
                 name: name.to_string(),
@@ -96,8 +173,11 @@ impl Environment {
         Ok(())
     }
 
-    /// Get a variable's value
+    /// Get a variable's value (or a constant's)
     pub fn get(&self, name: &str) -> Result<Value, NumerusError> {
+        if let Some((_, value)) = CONSTANTS.iter().find(|(constant, _)| *constant == name) {
+            return Ok(Value::Number(*value));
+        }
         self.variables.get(name).cloned().ok_or_else(|| {
             NumerusError::UndefinedVariable {
                 name: name.to_string(),
@@ -105,14 +185,24 @@ impl Environment {
         })
     }
 
+    /// Record the declared type of a variable
+    pub fn annotate(&mut self, name: &str, annotation: TypeAnnotation) {
+        self.annotations.insert(name.to_string(), annotation);
+    }
+
+    /// Get the declared type of a variable, if it was annotated
+    pub fn annotation(&self, name: &str) -> Option<TypeAnnotation> {
+        self.annotations.get(name).copied()
+    }
+
     /// Check if a variable exists
     pub fn contains(&self, name: &str) -> bool {
         self.variables.contains_key(name)
     }
 
-    /// Get all variable names (for debugging/REPL)
+    /// Get all variables in declaration order (for debugging/REPL)
     pub fn variables(&self) -> impl Iterator<Item = (&String, &Value)> {
-        self.variables.iter()
+        self.order.iter().map(|name| (name, &self.variables[name]))
     }
 }
 
@@ -134,6 +224,42 @@ mod tests {
         assert_eq!(env.get("msg").unwrap(), Value::String("Hello".to_string()));
     }
 
+    #[test]
+    fn test_variables_in_declaration_order() {
+        let mut env = Environment::new();
+        for name in ["ZETA", "ALPHA", "MU", "BETA", "OMEGA"] {
+            env.declare(name.to_string(), Value::Number(1)).unwrap();
+        }
+        env.assign("ALPHA", Value::Number(2)).unwrap();
+
+        let names: Vec<&str> = env.variables().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["ZETA", "ALPHA", "MU", "BETA", "OMEGA"]);
+        assert_eq!(env.variables().nth(1).unwrap().1, &Value::Number(2));
+    }
+
+    #[test]
+    fn test_constants_are_read_only() {
+        let mut env = Environment::new();
+        assert_eq!(env.get("MAXIMUS").unwrap(), Value::Number(3999));
+        assert_eq!(env.get("MINIMUS").unwrap(), Value::Number(1));
+        assert!(matches!(
+            env.declare("MAXIMUS".to_string(), Value::Number(5000)),
+            Err(NumerusError::AssignToConstant { .. })
+        ));
+        assert!(matches!(env.assign("MINIMUS", Value::Number(0)), Err(NumerusError::AssignToConstant { .. })));
+        assert_eq!(env.variables().count(), 0);
+    }
+
+    #[test]
+    fn test_define_replaces_without_error() {
+        let mut env = Environment::new();
+        env.define("X".to_string(), Value::Number(1));
+        env.define("X".to_string(), Value::Number(2));
+        assert_eq!(env.get("X").unwrap(), Value::Number(2));
+        assert_eq!(env.variables().count(), 1);
+        assert!(env.declare("X".to_string(), Value::Number(3)).is_err());
+    }
+
     #[test]
     fn test_declare_twice_fails() {
         let mut env = Environment::new();
@@ -1,12 +1,55 @@
 use super::{Environment, Value};
 use crate::error::NumerusError;
+use crate::lexer::Span;
 use crate::parser::*;
-use crate::roman::to_roman;
+use crate::roman::{from_roman, looks_like_roman, to_roman, to_roman_lower, NumeralMode, MAX_ROMAN};
+
+/// Default maximum number of evaluation steps before execution is aborted
+pub const DEFAULT_STEP_LIMIT: usize = 10_000_000;
+
+/// Default maximum length (in bytes) of a string produced by concatenation
+pub const DEFAULT_MAX_STRING_LENGTH: usize = 4 * 1024 * 1024;
+
+/// Default maximum number of elements in a list built by PROGRESSIO
+pub const DEFAULT_MAX_LIST_LENGTH: usize = 1_000_000;
+
+/// Roman names for 1/12 through 12/12 of a unit, as used by UNCIA
+const UNCIAE: [&str; 12] = [
+    "UNCIA", "SEXTANS", "QUADRANS", "TRIENS", "QUINCUNX", "SEMIS",
+    "SEPTUNX", "BES", "DODRANS", "DEXTANS", "DEUNX", "AS",
+];
+
+/// Callback invoked with each line printed by SCRIBE
+pub type PrintCallback = Box<dyn FnMut(&str)>;
+
+/// Looks up an OS environment variable for EX_AMBITU (`None` if unset)
+pub type EnvProvider = Box<dyn Fn(&str) -> Option<String>>;
+
+/// A non-fatal diagnostic raised while running a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub message: String,
+    pub span: Span,
+}
 
 /// The Numerus++ interpreter
 pub struct Interpreter {
     env: Environment,
     output: Vec<String>,
+    steps: usize,
+    max_steps: usize,
+    max_string_length: usize,
+    max_list_length: usize,
+    on_print: Option<PrintCallback>,
+    on_error_print: Option<PrintCallback>,
+    warn_on_truncation: bool,
+    warnings: Vec<Warning>,
+    ceremony_log: Vec<String>,
+    env_provider: EnvProvider,
+    unset_env_is_empty: bool,
+    digit_grouping: Option<char>,
+    numeral_mode: NumeralMode,
+    failed_statement: Option<Span>,
 }
 
 impl Interpreter {
@@ -14,12 +57,113 @@ impl Interpreter {
         Self {
             env: Environment::new(),
             output: Vec::new(),
+            steps: 0,
+            max_steps: DEFAULT_STEP_LIMIT,
+            max_string_length: DEFAULT_MAX_STRING_LENGTH,
+            max_list_length: DEFAULT_MAX_LIST_LENGTH,
+            on_print: None,
+            on_error_print: None,
+            warn_on_truncation: false,
+            warnings: Vec::new(),
+            ceremony_log: Vec::new(),
+            env_provider: Box::new(|name| std::env::var(name).ok()),
+            unset_env_is_empty: false,
+            digit_grouping: None,
+            numeral_mode: NumeralMode::Roman,
+            failed_statement: None,
         }
     }
 
+    /// Set the maximum number of statement and expression evaluations
+    /// allowed per run (a safety valve for untrusted programs)
+    pub fn set_step_limit(&mut self, limit: usize) {
+        self.max_steps = limit;
+    }
+
+    /// Set the maximum length (in bytes) a concatenated string may reach
+    pub fn set_max_string_length(&mut self, max: usize) {
+        self.max_string_length = max;
+    }
+
+    /// Set the maximum number of elements PROGRESSIO may produce
+    pub fn set_max_list_length(&mut self, max: usize) {
+        self.max_list_length = max;
+    }
+
+    /// Register a callback invoked synchronously with each line SCRIBE emits
+    ///
+    /// The interpreter never writes to stdout itself; output is only collected
+    /// (and returned by `run`) unless a callback such as
+    /// `Box::new(|line| println!("{}", line))` is registered here.
+    pub fn set_on_print(&mut self, callback: PrintCallback) {
+        self.on_print = Some(callback);
+    }
+
+    /// Register a callback invoked with each line SCRIBE_ERRATUM emits
+    ///
+    /// These lines are kept apart from SCRIBE output: they are not returned by
+    /// `run`, and are dropped unless a callback such as
+    /// `Box::new(|line| eprintln!("{}", line))` is registered here.
+    pub fn set_on_error_print(&mut self, callback: PrintCallback) {
+        self.on_error_print = Some(callback);
+    }
+
+    /// Emit a warning whenever DIVIDE discards a remainder (off by default)
+    pub fn set_warn_on_truncation(&mut self, enabled: bool) {
+        self.warn_on_truncation = enabled;
+    }
+
+    /// Replace where EX_AMBITU reads environment variables from (the process
+    /// environment by default)
+    pub fn set_env_provider(&mut self, provider: EnvProvider) {
+        self.env_provider = provider;
+    }
+
+    /// Make EX_AMBITU return an empty string for unset variables instead of
+    /// raising `InvalidFunctionArgument` (off by default)
+    pub fn set_unset_env_is_empty(&mut self, enabled: bool) {
+        self.unset_env_is_empty = enabled;
+    }
+
+    /// Separate thousands with `separator` when numbers are written in Arabic
+    /// digits, e.g. `Some('.')` gives `3.999` (off by default)
+    pub fn set_digit_grouping(&mut self, separator: Option<char>) {
+        self.digit_grouping = separator;
+    }
+
+    /// Print numbers in Arabic digits (`NumeralMode::ArabicOnly`) instead of
+    /// Roman numerals; lex the program in the same mode
+    pub fn set_numeral_mode(&mut self, mode: NumeralMode) {
+        self.numeral_mode = mode;
+    }
+
+    /// Span of the statement whose execution raised the last run's error, for
+    /// showing a multi-line statement in full alongside the error
+    pub fn failed_statement(&self) -> Option<Span> {
+        self.failed_statement
+    }
+
+    /// Warnings collected during the last run
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Notes left by `AVTEM("...")` during the last run (never printed)
+    pub fn ceremony_log(&self) -> &[String] {
+        &self.ceremony_log
+    }
+
     /// Run a program and return collected output
+    ///
+    /// The environment is kept between runs (as the REPL relies on), so running
+    /// a program that declares variables twice fails with `VariableAlreadyDeclared`;
+    /// use [`Interpreter::run_fresh`] to run independent programs.
     pub fn run(&mut self, program: &Program) -> Result<Vec<String>, NumerusError> {
         self.output.clear();
+        self.warnings.clear();
+        self.ceremony_log.clear();
+        self.steps = 0;
+        self.failed_statement = None;
 
         for statement in &program.statements {
             self.execute_statement(statement)?;
@@ -28,37 +172,181 @@ impl Interpreter {
         Ok(self.output.clone())
     }
 
+    /// Run a program, carrying on past statements that fail: returns the
+    /// collected output together with every error raised, in order
+    pub fn run_lenient(&mut self, program: &Program) -> (Vec<String>, Vec<NumerusError>) {
+        self.run_lenient_with(program, |_, _| {})
+    }
+
+    /// Like [`Interpreter::run_lenient`], but also passes each error and the
+    /// span of the statement that raised it to `on_error` as soon as the
+    /// statement fails, so it can be reported in line with the output so far
+    pub fn run_lenient_with(
+        &mut self,
+        program: &Program,
+        mut on_error: impl FnMut(&NumerusError, Span),
+    ) -> (Vec<String>, Vec<NumerusError>) {
+        self.output.clear();
+        self.warnings.clear();
+        self.ceremony_log.clear();
+        self.steps = 0;
+        self.failed_statement = None;
+
+        let mut errors = Vec::new();
+        for statement in &program.statements {
+            if let Err(error) = self.execute_statement(statement) {
+                on_error(&error, statement.span());
+                errors.push(error);
+            }
+        }
+
+        (self.output.clone(), errors)
+    }
+
+    /// Run a program against an empty environment, keeping the configuration
+    /// (limits, callbacks, warning settings) but none of the variables
+    pub fn run_fresh(&mut self, program: &Program) -> Result<Vec<String>, NumerusError> {
+        self.reset();
+        self.run(program)
+    }
+
+    /// Forget all variables and the results of previous runs
+    pub fn reset(&mut self) {
+        self.env = Environment::new();
+        self.output.clear();
+        self.warnings.clear();
+        self.ceremony_log.clear();
+        self.steps = 0;
+        self.failed_statement = None;
+    }
+
     /// Execute a single statement (for REPL mode)
     pub fn execute(&mut self, statement: &Statement) -> Result<Option<String>, NumerusError> {
         self.output.clear();
+        self.warnings.clear();
+        self.ceremony_log.clear();
+        self.steps = 0;
+        self.failed_statement = None;
         self.execute_statement(statement)?;
         Ok(self.output.pop())
     }
 
-    /// Execute a statement
+    /// Evaluate a single expression against the current environment
+    pub fn evaluate(&mut self, expression: &Expression) -> Result<Value, NumerusError> {
+        self.warnings.clear();
+        self.steps = 0;
+        self.evaluate_expression(expression)
+    }
+
+    /// Execute a statement, remembering it if it fails; errors raised without
+    /// a location (such as an arithmetic overflow) are pointed at the whole statement
     fn execute_statement(&mut self, stmt: &Statement) -> Result<(), NumerusError> {
+        self.execute_statement_body(stmt).map_err(|e| {
+            self.failed_statement = Some(stmt.span());
+            e.with_span(stmt.span())
+        })
+    }
+
+    fn execute_statement_body(&mut self, stmt: &Statement) -> Result<(), NumerusError> {
+        self.step()?;
+
         match stmt {
-            Statement::Declaration { name, value, .. } => {
+            Statement::Declaration { name, declared_type, value, span } => {
                 let val = self.evaluate_expression(value)?;
+                if let Some(annotation) = declared_type {
+                    check_annotation(name, *annotation, &val, *span)?;
+                }
                 self.env.declare(name.clone(), val)?;
+                if let Some(annotation) = declared_type {
+                    self.env.annotate(name, *annotation);
+                }
             }
 
-            Statement::Assignment { name, value, .. } => {
+            Statement::Assignment { name, value, span } => {
+                // Distinguish a missing DECLARA from a mistyped name being read
+                if !self.env.contains(name) && !Environment::is_constant(name) {
+                    return Err(NumerusError::AssignBeforeDeclare { name: name.clone(), span: *span });
+                }
                 let val = self.evaluate_expression(value)?;
+                if let Some(annotation) = self.env.annotation(name) {
+                    check_annotation(name, annotation, &val, *span)?;
+                }
                 self.env.assign(name, val)?;
             }
 
-            Statement::Print { value, .. } => {
+            Statement::Print { value, arguments, span } => {
+                let val = self.evaluate_expression(value)?;
+                let output = if arguments.is_empty() {
+                    self.output_string(&val).map_err(|e| e.with_span(value.span()))?
+                } else {
+                    let Value::String(template) = val else {
+                        return Err(NumerusError::TypeMismatch {
+                            operation: "SCRIBE".to_string(),
+                            expected: "format string".to_string(),
+                            span: value.span(),
+                        });
+                    };
+                    let mut values = Vec::with_capacity(arguments.len());
+                    for argument in arguments {
+                        values.push(self.evaluate_expression(argument)?);
+                    }
+                    // Specifier errors can point into the literal only when it has no escapes
+                    let literal = match value {
+                        Expression::StringLiteral { span, .. } if span.end - span.start == template.len() + 2 => {
+                            Some(*span)
+                        }
+                        _ => None,
+                    };
+                    format_template(&template, values.len(), literal, *span, |index, mode| {
+                        self.output_string_in(&values[index], mode.unwrap_or(self.numeral_mode))
+                            .map_err(|e| e.with_span(arguments[index].span()))
+                    })?
+                };
+                self.emit(output);
+            }
+
+            Statement::PrintError { value, .. } => {
                 let val = self.evaluate_expression(value)?;
-                let output = val.to_output_string()?;
-                println!("{}", output);
-                self.output.push(output);
+                let output = self.output_string(&val).map_err(|e| e.with_span(value.span()))?;
+                if let Some(on_error_print) = self.on_error_print.as_mut() {
+                    on_error_print(&output);
+                }
             }
 
-            Statement::Avtem { .. } => {
+            Statement::Table { start, end, span } => {
+                let (start, end) = match (self.evaluate_expression(start)?, self.evaluate_expression(end)?) {
+                    (Value::Number(start), Value::Number(end)) => (start, end),
+                    _ => {
+                        return Err(NumerusError::TypeMismatch {
+                            operation: "MONSTRA_TABULAM".to_string(),
+                            expected: "numbers".to_string(),
+                            span: *span,
+                        });
+                    }
+                };
+                if start < 1 || end > MAX_ROMAN || start > end {
+                    return Err(NumerusError::InvalidFunctionArgument {
+                        name: "MONSTRA_TABULAM".to_string(),
+                        span: *span,
+                    });
+                }
+
+                let width = group_digits(end, self.digit_grouping).len().max(3);
+                for n in start..=end {
+                    self.step()?;
+                    let roman = to_roman(n)?;
+                    let arabic = group_digits(n, self.digit_grouping);
+                    self.emit(format!("{:>width$} = {}", arabic, roman, width = width));
+                }
+            }
+
+            Statement::Avtem { message, .. } => {
                 // AVTEM - The ceremonial no-op
                 // In the spirit of Roman grandeur, this does absolutely nothing
-                // but adds tremendous swagger to your code
+                // but adds tremendous swagger to your code (and may leave a note)
+                if let Some(message) = message {
+                    self.ceremony_log.push(message.clone());
+                }
             }
 
             Statement::Comment { .. } => {
@@ -69,12 +357,24 @@ impl Interpreter {
     }
 
     /// Evaluate an expression to a Value
-    fn evaluate_expression(&self, expr: &Expression) -> Result<Value, NumerusError> {
+    fn evaluate_expression(&mut self, expr: &Expression) -> Result<Value, NumerusError> {
+        self.step()?;
+
         match expr {
             Expression::NumberLiteral { value, .. } => Ok(Value::Number(*value)),
 
             Expression::StringLiteral { value, .. } => Ok(Value::String(value.clone())),
 
+            Expression::BooleanLiteral { value, .. } => Ok(Value::Boolean(*value)),
+
+            Expression::ListLiteral { elements, .. } => {
+                let mut items = Vec::with_capacity(elements.len());
+                for element in elements {
+                    items.push(self.evaluate_expression(element)?);
+                }
+                Ok(Value::List(items))
+            }
+
             Expression::Variable { name, .. } => self.env.get(name),
 
             Expression::BinaryOp { left, operator, right, span } => {
@@ -90,21 +390,32 @@ impl Interpreter {
                                     .map(Value::Number)
                                     .ok_or(NumerusError::IntegerOverflow {
                                         value: *a as i64 + *b as i64,
+                                        span: None,
                                     })
                             }
                             (Value::String(a), Value::String(b)) => {
-                                Ok(Value::String(format!("{}{}", a, b)))
+                                self.concat(a, b)
                             }
                             (Value::String(a), Value::Number(b)) => {
                                 // String + Number: convert number to string (Roman by default)
-                                let num_str = to_roman(*b).unwrap_or_else(|_| b.to_string());
-                                Ok(Value::String(format!("{}{}", a, num_str)))
+                                self.concat(a, &self.number_text(*b))
                             }
                             (Value::Number(a), Value::String(b)) => {
                                 // Number + String: convert number to string (Roman by default)
-                                let num_str = to_roman(*a).unwrap_or_else(|_| a.to_string());
-                                Ok(Value::String(format!("{}{}", num_str, b)))
+                                self.concat(&self.number_text(*a), b)
                             }
+                            // String + Boolean (either side): append VERUM / FALSUM
+                            (Value::String(a), Value::Boolean(_)) => {
+                                self.concat(a, &self.output_string(&r)?)
+                            }
+                            (Value::Boolean(_), Value::String(b)) => {
+                                self.concat(&self.output_string(&l)?, b)
+                            }
+                            _ => Err(NumerusError::TypeMismatch {
+                                operation: "ADDIUS".to_string(),
+                                expected: "numbers or strings".to_string(),
+                                span: *span,
+                            })
                         }
                     }
                     BinaryOperator::Subtract => {
@@ -114,6 +425,7 @@ impl Interpreter {
                                     .map(Value::Number)
                                     .ok_or(NumerusError::IntegerOverflow {
                                         value: *a as i64 - *b as i64,
+                                        span: None,
                                     })
                             }
                             _ => Err(NumerusError::TypeMismatch {
@@ -125,13 +437,24 @@ impl Interpreter {
                     }
                     BinaryOperator::Multiply => {
                         match (&l, &r) {
+                            // Zero and one need no checked multiplication
+                            (Value::Number(0), Value::Number(_))
+                            | (Value::Number(_), Value::Number(0)) => Ok(Value::Number(0)),
+                            (Value::Number(1), Value::Number(n))
+                            | (Value::Number(n), Value::Number(1)) => Ok(Value::Number(*n)),
                             (Value::Number(a), Value::Number(b)) => {
                                 a.checked_mul(*b)
                                     .map(Value::Number)
                                     .ok_or(NumerusError::IntegerOverflow {
                                         value: *a as i64 * *b as i64,
+                                        span: None,
                                     })
                             }
+                            // String repetition: "ab" MULTIPLICA 3 = "ababab"
+                            (Value::String(text), Value::Number(count))
+                            | (Value::Number(count), Value::String(text)) => {
+                                self.repeat(text, *count, *span)
+                            }
                             _ => Err(NumerusError::TypeMismatch {
                                 operation: "MULTIPLICA".to_string(),
                                 expected: "numbers".to_string(),
@@ -144,8 +467,26 @@ impl Interpreter {
                             (Value::Number(a), Value::Number(b)) => {
                                 if *b == 0 {
                                     Err(NumerusError::DivisionByZero { span: *span })
+                                } else if *b == 1 {
+                                    Ok(Value::Number(*a))
                                 } else {
-                                    Ok(Value::Number(a / b))
+                                    // Only i32::MIN DIVIDE -1 overflows
+                                    let overflow = || NumerusError::IntegerOverflow {
+                                        value: i64::from(*a) / i64::from(*b),
+                                        span: None,
+                                    };
+                                    let quotient = a.checked_div(*b).ok_or_else(overflow)?;
+                                    let remainder = a.checked_rem(*b).ok_or_else(overflow)?;
+                                    if self.warn_on_truncation && remainder != 0 {
+                                        self.warnings.push(Warning {
+                                            message: format!(
+                                                "MONITUM: Divisio {} per {} truncata est (residuum {})!",
+                                                a, b, remainder
+                                            ),
+                                            span: *span,
+                                        });
+                                    }
+                                    Ok(Value::Number(quotient))
                                 }
                             }
                             _ => Err(NumerusError::TypeMismatch {
@@ -160,47 +501,356 @@ impl Interpreter {
 
             Expression::Grouped { inner, .. } => self.evaluate_expression(inner),
 
-            Expression::FunctionCall { function, argument, span } => {
-                let arg_value = self.evaluate_expression(argument)?;
-
-                match function {
-                    BuiltinFunction::Romaniza => {
-                        // ROMANIZA converts a number to its Roman string representation
-                        match arg_value {
-                            Value::Number(n) => {
-                                let roman = to_roman(n).map_err(|_| {
-                                    NumerusError::RomanOverflow { value: n }
-                                })?;
-                                Ok(Value::String(roman))
-                            }
-                            Value::String(_) => Err(NumerusError::TypeMismatch {
-                                operation: "ROMANIZA".to_string(),
-                                expected: "number".to_string(),
-                                span: *span,
-                            })
-                        }
-                    }
-                    BuiltinFunction::Arabiza => {
-                        // ARABIZA converts a number to its Arabic string representation
-                        // This allows displaying numbers as Arabic when concatenating or printing
-                        match arg_value {
-                            Value::Number(n) => {
-                                Ok(Value::String(n.to_string()))
-                            }
-                            Value::String(_) => Err(NumerusError::TypeMismatch {
-                                operation: "ARABIZA".to_string(),
-                                expected: "number".to_string(),
-                                span: *span,
-                            })
-                        }
-                    }
-                    BuiltinFunction::Exprime => {
-                        // EXPRIME returns value as-is for now
-                        Ok(arg_value)
-                    }
+            Expression::FunctionCall { function, arguments, span } => {
+                let mut args = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    args.push(self.evaluate_expression(argument)?);
+                }
+
+                self.call_builtin(*function, &args, *span)
+            }
+        }
+    }
+
+    /// Call a built-in function with already-evaluated arguments
+    fn call_builtin(
+        &mut self,
+        function: BuiltinFunction,
+        args: &[Value],
+        span: Span,
+    ) -> Result<Value, NumerusError> {
+        let type_mismatch = |expected: &str| NumerusError::TypeMismatch {
+            operation: function.name().to_string(),
+            expected: expected.to_string(),
+            span,
+        };
+        let invalid_argument = || NumerusError::InvalidFunctionArgument {
+            name: function.name().to_string(),
+            span,
+        };
+
+        match (function, args) {
+            // ROMANIZA converts a number to its Roman string representation
+            (BuiltinFunction::Romaniza, [Value::Number(n)]) => {
+                let roman = to_roman(*n).map_err(|e| NumerusError::from(e).with_span(span))?;
+                Ok(Value::String(roman))
+            }
+            (BuiltinFunction::Romaniza, [_]) => Err(type_mismatch("number")),
+
+            // ARABIZA converts a number to its Arabic string representation
+            // This allows displaying numbers as Arabic when concatenating or printing
+            (BuiltinFunction::Arabiza, [Value::Number(n)]) => {
+                Ok(Value::String(group_digits(*n, self.digit_grouping)))
+            }
+            (BuiltinFunction::Arabiza, [_]) => Err(type_mismatch("number")),
+
+            // EXPRIME reads a number out of a string written in Roman numerals or
+            // Arabic digits (numbers pass through unchanged)
+            (BuiltinFunction::Exprime, [Value::String(text)]) => {
+                let text = text.trim();
+                if looks_like_roman(text) {
+                    from_roman(text)
+                        .map(Value::Number)
+                        .map_err(|e| NumerusError::from(e).with_span(span))
+                } else {
+                    text.parse().map(Value::Number).map_err(|_| invalid_argument())
+                }
+            }
+            (BuiltinFunction::Exprime, [Value::Number(n)]) => Ok(Value::Number(*n)),
+            (BuiltinFunction::Exprime, [_]) => Err(type_mismatch("a string or a number")),
+
+            // COMPARA returns -1, 0 or 1 depending on the ordering of its arguments
+            (BuiltinFunction::Compara, [a, b]) => match a.partial_cmp(b) {
+                Some(ordering) => Ok(Value::Number(ordering as i32)),
+                None => Err(type_mismatch("two values of the same type")),
+            },
+
+            // PRIMUS tests whether a number is prime by trial division
+            (BuiltinFunction::Primus, [Value::Number(n)]) => {
+                if *n < 1 {
+                    return Err(invalid_argument());
+                }
+                Ok(Value::Boolean(is_prime(*n)))
+            }
+            (BuiltinFunction::Primus, [_]) => Err(type_mismatch("number")),
+
+            // FACTORES lists the prime factors of a number in ascending order
+            (BuiltinFunction::Factores, [Value::Number(n)]) => {
+                if *n < 1 {
+                    return Err(invalid_argument());
+                }
+                Ok(Value::List(prime_factors(*n).into_iter().map(Value::Number).collect()))
+            }
+            (BuiltinFunction::Factores, [_]) => Err(type_mismatch("number")),
+
+            // PROGRESSIO lists start, start + gradus, ... up to and including finis
+            (
+                BuiltinFunction::Progressio,
+                [Value::Number(start), Value::Number(end), Value::Number(step)],
+            ) => {
+                let (start, end, step) = (*start, *end, *step);
+                if step == 0 || (start < end && step < 0) || (start > end && step > 0) {
+                    return Err(invalid_argument());
+                }
+
+                // Check the length before allocating anything
+                let len = (i64::from(end) - i64::from(start)) / i64::from(step) + 1;
+                let len = usize::try_from(len).unwrap_or(usize::MAX);
+                if len > self.max_list_length {
+                    return Err(NumerusError::ListTooLong { len, max: self.max_list_length });
+                }
+
+                let mut items = Vec::with_capacity(len);
+                let mut current = Some(start);
+                while let Some(n) = current.filter(|n| if step > 0 { *n <= end } else { *n >= end }) {
+                    self.step()?;
+                    items.push(Value::Number(n));
+                    current = n.checked_add(step);
+                }
+                Ok(Value::List(items))
+            }
+            (BuiltinFunction::Progressio, [_, _, _]) => Err(type_mismatch("numbers")),
+
+            // UNCIA names n twelfths the way the Romans did (6 = SEMIS, 12 = AS)
+            (BuiltinFunction::Uncia, [Value::Number(n)]) => {
+                let name = usize::try_from(*n)
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .and_then(|i| UNCIAE.get(i))
+                    .ok_or_else(invalid_argument)?;
+                Ok(Value::String(name.to_string()))
+            }
+            (BuiltinFunction::Uncia, [_]) => Err(type_mismatch("number")),
+
+            // SORTA returns a sorted copy of a list of numbers or of strings,
+            // in descending order when the optional second argument is VERUM
+            (BuiltinFunction::Sorta, [Value::List(items), rest @ ..]) => {
+                let descending = match rest {
+                    [] => false,
+                    [Value::Boolean(descending)] => *descending,
+                    _ => return Err(type_mismatch("a list and an optional boolean")),
+                };
+
+                let comparable = items.windows(2).all(|pair| pair[0].partial_cmp(&pair[1]).is_some());
+                if !comparable || items.iter().any(|item| !item.is_number() && !item.is_string()) {
+                    return Err(type_mismatch("a list of numbers or of strings"));
+                }
+
+                let mut sorted = items.clone();
+                sorted.sort_by(|a, b| a.partial_cmp(b).expect("list checked to be comparable"));
+                if descending {
+                    sorted.reverse();
+                }
+                Ok(Value::List(sorted))
+            }
+            (BuiltinFunction::Sorta, [_] | [_, _]) => Err(type_mismatch("a list")),
+
+            // ADDE returns a copy of the list with the value appended
+            (BuiltinFunction::Adde, [Value::List(items), value]) => {
+                let mut items = items.clone();
+                items.push(value.clone());
+                Ok(Value::List(items))
+            }
+            (BuiltinFunction::Adde, [_, _]) => Err(type_mismatch("a list")),
+
+            // REMOVE returns a copy of the list without the element at a 1-based index
+            (BuiltinFunction::Remove, [Value::List(items), Value::Number(index)]) => {
+                let position = usize::try_from(*index)
+                    .ok()
+                    .and_then(|i| i.checked_sub(1))
+                    .filter(|&i| i < items.len())
+                    .ok_or(NumerusError::IndexOutOfBounds { index: *index, len: items.len(), span })?;
+                let mut items = items.clone();
+                items.remove(position);
+                Ok(Value::List(items))
+            }
+            (BuiltinFunction::Remove, [_, _]) => Err(type_mismatch("a list and a number")),
+
+            // EX_AMBITU reads an environment variable through the configured provider
+            (BuiltinFunction::ExAmbitu, [Value::String(name)]) => match (self.env_provider)(name) {
+                Some(value) => Ok(Value::String(value)),
+                None if self.unset_env_is_empty => Ok(Value::String(String::new())),
+                None => Err(invalid_argument()),
+            },
+            (BuiltinFunction::ExAmbitu, [_]) => Err(type_mismatch("string")),
+
+            // HEX reads a hexadecimal string (e.g. "FF") as a number: I–MMMCMXCIX,
+            // or any non-negative i32 under NumeralMode::ArabicOnly
+            (BuiltinFunction::Hex, [Value::String(digits)]) => {
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(invalid_argument());
+                }
+                let (range, min, max) = match self.numeral_mode {
+                    NumeralMode::Roman => ("1–3999 / I–MMMCMXCIX", 1, MAX_ROMAN),
+                    NumeralMode::ArabicOnly => ("0–7FFFFFFF", 0, i32::MAX),
+                };
+                // Too many digits for an i64 is out of range just the same
+                i64::from_str_radix(digits, 16)
+                    .ok()
+                    .and_then(|value| i32::try_from(value).ok())
+                    .filter(|value| (min..=max).contains(value))
+                    .map(Value::Number)
+                    .ok_or_else(|| NumerusError::HexOutOfRange { digits: digits.clone(), range, span })
+            }
+            (BuiltinFunction::Hex, [_]) => Err(type_mismatch("string")),
+
+            // VERTE reverses a string character by character, or a list
+            (BuiltinFunction::Verte, [Value::String(s)]) => Ok(Value::String(s.chars().rev().collect())),
+            (BuiltinFunction::Verte, [Value::List(items)]) => {
+                Ok(Value::List(items.iter().rev().cloned().collect()))
+            }
+            (BuiltinFunction::Verte, [_]) => Err(type_mismatch("a string or a list")),
+
+            // DIVIDE_CUM_RESIDUO returns both halves of an integer division: {quotient, remainder}
+            (BuiltinFunction::DivideCumResiduo, [Value::Number(a), Value::Number(b)]) => {
+                if *b == 0 {
+                    return Err(NumerusError::DivisionByZero { span });
+                }
+                let overflow = || NumerusError::IntegerOverflow { value: -i64::from(*a), span: None };
+                let quotient = a.checked_div(*b).ok_or_else(overflow)?;
+                let remainder = a.checked_rem(*b).ok_or_else(overflow)?;
+                Ok(Value::List(vec![Value::Number(quotient), Value::Number(remainder)]))
+            }
+            (BuiltinFunction::DivideCumResiduo, [_, _]) => Err(type_mismatch("numbers")),
+
+            // DUPLICA doubles a number; DIMIDIUM halves it (integer division)
+            (BuiltinFunction::Duplica, [Value::Number(n)]) => n
+                .checked_mul(2)
+                .map(Value::Number)
+                .ok_or(NumerusError::IntegerOverflow { value: i64::from(*n) * 2, span: None }),
+            (BuiltinFunction::Duplica, [_]) => Err(type_mismatch("number")),
+            (BuiltinFunction::Dimidium, [Value::Number(n)]) => Ok(Value::Number(n / 2)),
+            (BuiltinFunction::Dimidium, [_]) => Err(type_mismatch("number")),
+
+            // ROMANIZA_MINUSCULA is ROMANIZA in lowercase (xlii), as for page numbers
+            (BuiltinFunction::RomanizaMinuscula, [Value::Number(n)]) => {
+                let roman = to_roman_lower(*n).map_err(|e| NumerusError::from(e).with_span(span))?;
+                Ok(Value::String(roman))
+            }
+            (BuiltinFunction::RomanizaMinuscula, [_]) => Err(type_mismatch("number")),
+
+            // SUMMA_USQUE sums 1..=n and SUMMA_INTER sums a..=b without building a list;
+            // the result may exceed MMMCMXCIX (print it with ARABIZA)
+            (BuiltinFunction::SummaUsque, [Value::Number(n)]) => {
+                if *n < 0 {
+                    return Err(invalid_argument());
+                }
+                series_sum(1, *n)
+            }
+            (BuiltinFunction::SummaUsque, [_]) => Err(type_mismatch("number")),
+            (BuiltinFunction::SummaInter, [Value::Number(a), Value::Number(b)]) => {
+                if a > b {
+                    return Err(invalid_argument());
                 }
+                series_sum(*a, *b)
+            }
+            (BuiltinFunction::SummaInter, [_, _]) => Err(type_mismatch("numbers")),
+
+            // INSPICE prints its argument (like dbg!) and passes it through unchanged
+            (BuiltinFunction::Inspice, [value]) => {
+                let shown = self.output_string(value).map_err(|e| e.with_span(span))?;
+                self.emit(format!("[inspectio] VALOR = {}", shown));
+                Ok(value.clone())
+            }
+
+            // TYPUS names the type of any value, as written in type annotations
+            (BuiltinFunction::Typus, [value]) => Ok(Value::String(value.type_name().to_string())),
+
+            // PAR and IMPAR test whether a number is even or odd
+            (BuiltinFunction::Par, [Value::Number(n)]) => Ok(Value::Boolean(n % 2 == 0)),
+            (BuiltinFunction::Par, [_]) => Err(type_mismatch("number")),
+            (BuiltinFunction::Impar, [Value::Number(n)]) => Ok(Value::Boolean(n % 2 != 0)),
+            (BuiltinFunction::Impar, [_]) => Err(type_mismatch("number")),
+
+            (function, args) => Err(NumerusError::WrongArgumentCount {
+                name: function.name().to_string(),
+                expected: function.arity(),
+                found: args.len(),
+                span,
+            }),
+        }
+    }
+
+    /// Send a line of program output to the print callback and the collected output
+    fn emit(&mut self, output: String) {
+        if let Some(on_print) = self.on_print.as_mut() {
+            on_print(&output);
+        }
+        self.output.push(output);
+    }
+
+    /// Render a value for SCRIBE: as `Value::to_output_string`, but in Arabic
+    /// digits under `NumeralMode::ArabicOnly`
+    pub fn output_string(&self, value: &Value) -> Result<String, NumerusError> {
+        self.output_string_in(value, self.numeral_mode)
+    }
+
+    /// Render a value for SCRIBE in the given numeral mode
+    fn output_string_in(&self, value: &Value, mode: NumeralMode) -> Result<String, NumerusError> {
+        match (mode, value) {
+            (NumeralMode::ArabicOnly, Value::Number(n)) => Ok(group_digits(*n, self.digit_grouping)),
+            (NumeralMode::ArabicOnly, Value::List(items)) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.output_string_in(item, mode))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(format!("{{{}}}", items.join(", ")))
             }
+            _ => value.to_output_string(),
+        }
+    }
+
+    /// Render a number joined onto a string: Roman where possible, else Arabic
+    fn number_text(&self, n: i32) -> String {
+        match self.numeral_mode {
+            NumeralMode::Roman => to_roman(n).unwrap_or_else(|_| n.to_string()),
+            NumeralMode::ArabicOnly => group_digits(n, self.digit_grouping),
+        }
+    }
+
+    /// Concatenate two strings, checking the length cap before allocating
+    fn concat(&self, a: &str, b: &str) -> Result<Value, NumerusError> {
+        let len = a.len() + b.len();
+        if len > self.max_string_length {
+            return Err(NumerusError::StringTooLong { len, max: self.max_string_length });
+        }
+
+        let mut result = String::with_capacity(len);
+        result.push_str(a);
+        result.push_str(b);
+        Ok(Value::String(result))
+    }
+
+    /// Repeat a string `count` times, checking the length cap before allocating
+    fn repeat(&self, text: &str, count: i32, span: Span) -> Result<Value, NumerusError> {
+        if count < 0 {
+            return Err(NumerusError::NegativeRepetition { count, span });
+        }
+
+        let len = text.len().saturating_mul(count as usize);
+        if len > self.max_string_length {
+            return Err(NumerusError::StringTooLong { len, max: self.max_string_length });
+        }
+
+        Ok(Value::String(text.repeat(count as usize)))
+    }
+
+    /// Count one evaluation step, failing once the step limit is exceeded
+    fn step(&mut self) -> Result<(), NumerusError> {
+        self.steps += 1;
+        if self.steps > self.max_steps {
+            return Err(NumerusError::StepLimitExceeded { limit: self.max_steps });
         }
+        Ok(())
+    }
+
+    /// Make a host-provided value available to scripts as variable `name`
+    ///
+    /// Calling this again for the same name replaces the value. Scripts can read
+    /// and assign the variable, but `DECLARA` of the same name is an error
+    /// (`VariableAlreadyDeclared`), exactly as for a script-declared variable.
+    pub fn declare_external(&mut self, name: &str, value: Value) {
+        self.env.define(name.to_string(), value);
     }
 
     /// Get the environment (for testing/debugging)
@@ -209,6 +859,158 @@ impl Interpreter {
     }
 }
 
+/// Substitute arguments into the placeholders of a SCRIBE format string
+///
+/// `{N}` refers to argument N; `{}` (or a name such as `{X}`) takes the next
+/// argument in order. Every argument must be used and every placeholder filled.
+/// A placeholder may end in `:a` or `:r` to print that argument in Arabic or
+/// Roman numerals; `render` turns argument N into text in the chosen mode.
+/// `literal` is the span of the string literal the template was written as,
+/// if it appears there verbatim, so a bad specifier can be pointed at.
+fn format_template(
+    template: &str,
+    count: usize,
+    literal: Option<Span>,
+    span: Span,
+    mut render: impl FnMut(usize, Option<NumeralMode>) -> Result<String, NumerusError>,
+) -> Result<String, NumerusError> {
+    let mut result = String::new();
+    let mut used = vec![false; count];
+    let mut needed = 0;
+    let mut next_implicit = 0;
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        result.push_str(&rest[..open]);
+
+        let (placeholder, specifier) = match rest[open + 1..close].split_once(':') {
+            Some((placeholder, specifier)) => (placeholder, Some(specifier)),
+            None => (&rest[open + 1..close], None),
+        };
+        let mode = match specifier {
+            None => None,
+            Some("a") => Some(NumeralMode::ArabicOnly),
+            Some("r") => Some(NumeralMode::Roman),
+            Some(specifier) => {
+                let offset = template.len() - rest.len() + close - specifier.len();
+                let span = literal.map_or(span, |literal| Span::new(
+                    literal.start + 1 + offset,
+                    literal.start + 1 + offset + specifier.len(),
+                    literal.line,
+                    literal.column + 1 + template[..offset].chars().count(),
+                ));
+                return Err(NumerusError::InvalidFormatSpecifier { specifier: specifier.to_string(), span });
+            }
+        };
+        let index = match placeholder.parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => {
+                next_implicit += 1;
+                next_implicit - 1
+            }
+        };
+        needed = needed.max(index.saturating_add(1));
+
+        if index < count {
+            result.push_str(&render(index, mode)?);
+            used[index] = true;
+        }
+        rest = &rest[close + 1..];
+    }
+    result.push_str(rest);
+
+    if needed != count || used.contains(&false) {
+        return Err(NumerusError::FormatArgumentMismatch {
+            placeholders: needed,
+            arguments: count,
+            span,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Ensure a value matches the declared type of the variable it is bound to
+fn check_annotation(
+    name: &str,
+    annotation: TypeAnnotation,
+    value: &Value,
+    span: Span,
+) -> Result<(), NumerusError> {
+    if value.matches_type(annotation) {
+        Ok(())
+    } else {
+        Err(NumerusError::TypeAnnotationMismatch {
+            name: name.to_string(),
+            expected: annotation.name().to_string(),
+            found: value.type_name().to_string(),
+            span,
+        })
+    }
+}
+
+/// Write `n` in Arabic digits, separating thousands with `separator` if given
+fn group_digits(n: i32, separator: Option<char>) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if n < 0 {
+        grouped.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if let Some(separator) = separator.filter(|_| i > 0 && (digits.len() - i).is_multiple_of(3)) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Sum of the whole numbers `first..=last` (an arithmetic series), checked
+/// against overflow
+fn series_sum(first: i32, last: i32) -> Result<Value, NumerusError> {
+    let (first, last) = (i128::from(first), i128::from(last));
+    let sum = (last - first + 1) * (first + last) / 2;
+    i32::try_from(sum).map(Value::Number).map_err(|_| NumerusError::IntegerOverflow {
+        value: i64::try_from(sum).unwrap_or(i64::MAX),
+        span: None,
+    })
+}
+
+/// Whether `n` is prime, by trial division up to its square root
+fn is_prime(n: i32) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut divisor = 2;
+    while divisor <= n / divisor {
+        if n % divisor == 0 {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
+/// Prime factors of `n` in ascending order, with repetition (`1` has none)
+fn prime_factors(mut n: i32) -> Vec<i32> {
+    let mut factors = Vec::new();
+    let mut divisor = 2;
+    while divisor <= n / divisor {
+        while n % divisor == 0 {
+            factors.push(divisor);
+            n /= divisor;
+        }
+        divisor += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
 impl Default for Interpreter {
     fn default() -> Self {
         Self::new()
@@ -218,6 +1020,9 @@ impl Default for Interpreter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::error::format_error_with_context;
     use crate::lexer::Lexer;
     use crate::parser::Parser;
 
@@ -306,6 +1111,26 @@ mod tests {
         assert_eq!(env.get("C").unwrap(), Value::Number(7));
     }
 
+    #[test]
+    fn test_multiply_divide_identities() {
+        let (_, env) = run_and_get_env(
+            "DECLARA HUGE EST MMM MULTIPLICA MMM MULTIPLICA XC\n\
+             DECLARA A EST HUGE MULTIPLICA 0\n\
+             DECLARA B EST 0 MULTIPLICA HUGE\n\
+             DECLARA F EST HUGE MULTIPLICA 1\n\
+             DECLARA G EST 1 MULTIPLICA XLII\n\
+             DECLARA H EST HUGE DIVIDE 1",
+        );
+        assert_eq!(env.get("A").unwrap(), Value::Number(0));
+        assert_eq!(env.get("B").unwrap(), Value::Number(0));
+        assert_eq!(env.get("F").unwrap(), Value::Number(810_000_000));
+        assert_eq!(env.get("G").unwrap(), Value::Number(42));
+        assert_eq!(env.get("H").unwrap(), Value::Number(810_000_000));
+
+        let program = Parser::new(Lexer::new("SCRIBE(1 DIVIDE 0)").tokenize().unwrap()).parse().unwrap();
+        assert!(matches!(Interpreter::new().run(&program), Err(NumerusError::DivisionByZero { .. })));
+    }
+
     #[test]
     fn test_precedence() {
         // 2 + 3 * 4 = 2 + 12 = 14
@@ -346,6 +1171,23 @@ SCRIBE(ARABIZA(X))"#);
         assert_eq!(output[0], "Hello World");
     }
 
+    #[test]
+    fn test_print_empty_string() {
+        assert_eq!(run(r#"SCRIBE("")"#), vec![""]);
+        assert_eq!(run(r#"SCRIBE("a")
+SCRIBE("")
+SCRIBE("" ADDIUS "")"#), vec!["a", "", ""]);
+
+        // The print callback also sees the blank line
+        let program = Parser::new(Lexer::new(r#"SCRIBE("")"#).tokenize().unwrap()).parse().unwrap();
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&printed);
+        let mut interpreter = Interpreter::new();
+        interpreter.set_on_print(Box::new(move |line| sink.borrow_mut().push(line.to_string())));
+        interpreter.run(&program).unwrap();
+        assert_eq!(*printed.borrow(), vec![String::new()]);
+    }
+
     #[test]
     fn test_print_concat() {
         let output = run(r#"DECLARA X EST 42
@@ -395,4 +1237,862 @@ DECLARA D EST C DIVIDE 5
         let result = interpreter.run(&program);
         assert!(matches!(result, Err(NumerusError::UndefinedVariable { .. })));
     }
+
+    #[test]
+    fn test_assign_before_declare() {
+        let program = Parser::new(Lexer::new("DECLARA A EST XV\nSUMMA EST A").tokenize().unwrap()).parse().unwrap();
+        let error = Interpreter::new().run(&program).unwrap_err();
+        assert!(matches!(
+            &error,
+            NumerusError::AssignBeforeDeclare { name, span } if name == "SUMMA" && span.line == 2
+        ));
+        assert!(error.to_string().contains("DECLARA SUMMA EST"), "{}", error);
+    }
+
+    #[test]
+    fn test_maximus_minimus_constants() {
+        let output = run("DECLARA X EST MAXIMUS SUBTRAHE MINIMUS\nSCRIBE(X)\nSCRIBE(ARABIZA(MAXIMUS))");
+        assert_eq!(output, vec!["MMMCMXCVIII", "3999"]);
+
+        for source in ["MAXIMUS EST MM", "DECLARA MINIMUS EST 0"] {
+            let program = Parser::new(Lexer::new(source).tokenize().unwrap()).parse().unwrap();
+            let error = Interpreter::new().run(&program).unwrap_err();
+            assert!(matches!(error, NumerusError::AssignToConstant { span: Some(_), .. }), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_step_limit_exceeded() {
+        let mut lexer = Lexer::new("DECLARA X EST 1 ADDIUS 2 ADDIUS 3\nSCRIBE(X)");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_step_limit(3);
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::StepLimitExceeded { limit: 3 })));
+    }
+
+    #[test]
+    fn test_step_limit_not_reached() {
+        let mut lexer = Lexer::new("DECLARA X EST 1 ADDIUS 2\nSCRIBE(X)");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_step_limit(10);
+        let output = interpreter.run(&program).unwrap();
+        assert_eq!(output, vec!["III".to_string()]);
+    }
+
+    #[test]
+    fn test_string_too_long() {
+        let mut lexer = Lexer::new(r#"DECLARA msg EST "Salve, " ADDIUS "Munde!""#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_max_string_length(10);
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::StringTooLong { len: 13, max: 10 })));
+    }
+
+    #[test]
+    fn test_typed_declaration() {
+        let (_, env) = run_and_get_env("DECLARA X: NUMERUS EST XV ADDIUS 1\nDECLARA S: CHORDA EST \"hi\"\nX EST 10");
+        assert_eq!(env.get("X").unwrap(), Value::Number(10));
+        assert_eq!(env.get("S").unwrap(), Value::String("hi".to_string()));
+    }
+
+    #[test]
+    fn test_typed_declaration_mismatch() {
+        let mut lexer = Lexer::new(r#"DECLARA X: NUMERUS EST "quinque""#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::TypeAnnotationMismatch { .. })));
+    }
+
+    #[test]
+    fn test_typed_reassignment_mismatch() {
+        let mut lexer = Lexer::new("DECLARA S: CHORDA EST \"hi\"\nS EST 42");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::TypeAnnotationMismatch { .. })));
+    }
+
+    #[test]
+    fn test_print_format_positional() {
+        let output = run(r#"DECLARA X EST 42
+SCRIBE("Valor: {}", ARABIZA(X))
+SCRIBE("{1} post {0}", X, "finis")
+SCRIBE("Valor: {X}", X)"#);
+        assert_eq!(output, vec!["Valor: 42", "finis post XLII", "Valor: XLII"]);
+    }
+
+    #[test]
+    fn test_print_format_specifiers() {
+        let output = run(r#"DECLARA X EST 42
+SCRIBE("{X:a} = {X:r}", X, X)
+SCRIBE("{0:a} = {0:r} = {0}", X)"#);
+        assert_eq!(output, vec!["42 = XLII", "42 = XLII = XLII"]);
+
+        let source = r#"SCRIBE("{:a} et {:q}", 1, 2)"#;
+        let program = Parser::new(Lexer::new(source).tokenize().unwrap()).parse().unwrap();
+        let error = Interpreter::new().run(&program).unwrap_err();
+        let NumerusError::InvalidFormatSpecifier { specifier, span } = error else {
+            panic!("expected InvalidFormatSpecifier, got {:?}", error);
+        };
+        assert_eq!(specifier, "q");
+        assert_eq!(&source[span.start..span.end], "q");
+        assert_eq!(span.column, span.start + 1);
+    }
+
+    #[test]
+    fn test_print_format_count_mismatch() {
+        let mut lexer = Lexer::new(r#"SCRIBE("{} et {}", 1)"#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(
+            result,
+            Err(NumerusError::FormatArgumentMismatch { placeholders: 2, arguments: 1, .. })
+        ));
+
+        // An index too large to count past is simply one more missing argument
+        let program = Parser::new(Lexer::new(r#"SCRIBE("{18446744073709551615}", 1)"#).tokenize().unwrap())
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            Interpreter::new().run(&program),
+            Err(NumerusError::FormatArgumentMismatch { placeholders: usize::MAX, arguments: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_string_repetition() {
+        let (_, env) = run_and_get_env(r#"DECLARA A EST "ab" MULTIPLICA 3
+DECLARA B EST III MULTIPLICA "-"
+DECLARA C EST "ab" MULTIPLICA 0"#);
+        assert_eq!(env.get("A").unwrap(), Value::String("ababab".to_string()));
+        assert_eq!(env.get("B").unwrap(), Value::String("---".to_string()));
+        assert_eq!(env.get("C").unwrap(), Value::String(String::new()));
+    }
+
+    #[test]
+    fn test_string_repetition_negative() {
+        let mut lexer = Lexer::new(r#"DECLARA A EST "ab" MULTIPLICA (1 SUBTRAHE 2)"#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::NegativeRepetition { count: -1, .. })));
+    }
+
+    #[test]
+    fn test_string_repetition_too_long() {
+        let mut lexer = Lexer::new(r#"DECLARA A EST "abc" MULTIPLICA 4"#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_max_string_length(10);
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::StringTooLong { len: 12, max: 10 })));
+    }
+
+    #[test]
+    fn test_on_print_callback() {
+        let mut lexer = Lexer::new("SCRIBE(\"primus\")\nAVTEM\nSCRIBE(XLII)");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&printed);
+        let mut interpreter = Interpreter::new();
+        interpreter.set_on_print(Box::new(move |line| sink.borrow_mut().push(line.to_string())));
+        interpreter.run(&program).unwrap();
+
+        assert_eq!(*printed.borrow(), vec!["primus".to_string(), "XLII".to_string()]);
+    }
+
+    #[test]
+    fn test_scribe_erratum_goes_to_error_sink() {
+        let program = Parser::new(Lexer::new("SCRIBE(\"primus\")\nSCRIBE_ERRATUM(\"cave: \" ADDIUS XLII)\nSCRIBE(II)")
+            .tokenize().unwrap()).parse().unwrap();
+
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let (print_sink, error_sink) = (Rc::clone(&printed), Rc::clone(&errors));
+        let mut interpreter = Interpreter::new();
+        interpreter.set_on_print(Box::new(move |line| print_sink.borrow_mut().push(line.to_string())));
+        interpreter.set_on_error_print(Box::new(move |line| error_sink.borrow_mut().push(line.to_string())));
+
+        let output = interpreter.run(&program).unwrap();
+        assert_eq!(output, vec!["primus", "II"]);
+        assert_eq!(*printed.borrow(), vec!["primus".to_string(), "II".to_string()]);
+        assert_eq!(*errors.borrow(), vec!["cave: XLII".to_string()]);
+
+        // Without a callback the line is simply dropped
+        assert_eq!(Interpreter::new().run(&program).unwrap(), vec!["primus", "II"]);
+    }
+
+    #[test]
+    fn test_truncation_warning() {
+        let mut lexer = Lexer::new("DECLARA A EST 7 DIVIDE 2\nDECLARA B EST 6 DIVIDE 2");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_warn_on_truncation(true);
+        interpreter.run(&program).unwrap();
+
+        assert_eq!(interpreter.warnings().len(), 1);
+        assert_eq!(interpreter.warnings()[0].span.line, 1);
+        assert_eq!(interpreter.environment().get("A").unwrap(), Value::Number(3));
+    }
+
+    #[test]
+    fn test_avtem_ceremony_log() {
+        let mut lexer = Lexer::new("AVTEM\nAVTEM(\"initium\")\nSCRIBE(XLII)\nAVTEM(\"finis\")");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let output = interpreter.run(&program).unwrap();
+
+        assert_eq!(output, vec!["XLII"]);
+        assert_eq!(interpreter.ceremony_log(), ["initium", "finis"]);
+    }
+
+    #[test]
+    fn test_truncation_warning_off_by_default() {
+        let mut lexer = Lexer::new("DECLARA A EST 7 DIVIDE 2");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.run(&program).unwrap();
+        assert!(interpreter.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_run_empty_program() {
+        assert!(run("").is_empty());
+        assert!(run("\n\n").is_empty());
+        assert!(run("NOTA: nihil agendum\nNOTA: etiam nihil").is_empty());
+    }
+
+    #[test]
+    fn test_negative_result_printed_in_roman_has_span() {
+        let mut lexer = Lexer::new("DECLARA X EST 5 SUBTRAHE 10\nSCRIBE(X MULTIPLICA 1)");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        match result {
+            Err(NumerusError::NegativeRomanConversion { value: -5, span: Some(span) }) => {
+                assert_eq!((span.line, span.column), (2, 8));
+            }
+            other => panic!("Expected spanned NegativeRomanConversion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_negative_result_printed_in_arabic() {
+        let output = run("SCRIBE(ARABIZA(5 SUBTRAHE 10))");
+        assert_eq!(output, vec!["-5"]);
+    }
+
+    #[test]
+    fn test_compara_numbers() {
+        let output = run("SCRIBE(ARABIZA(COMPARA(II, XX)))\nSCRIBE(ARABIZA(COMPARA(XX, 20)))\nSCRIBE(ARABIZA(COMPARA(XL, 4)))");
+        assert_eq!(output, vec!["-1", "0", "1"]);
+    }
+
+    #[test]
+    fn test_compara_strings() {
+        let output = run(r#"SCRIBE(ARABIZA(COMPARA("alpha", "beta")))
+SCRIBE(ARABIZA(COMPARA("roma", "roma")))
+SCRIBE(ARABIZA(COMPARA("zeta", "beta")))"#);
+        assert_eq!(output, vec!["-1", "0", "1"]);
+    }
+
+    #[test]
+    fn test_compara_mixed_types() {
+        let mut lexer = Lexer::new(r#"DECLARA X EST COMPARA(1, "I")"#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_primus() {
+        let output = run("SCRIBE(PRIMUS(XCVII))\nSCRIBE(PRIMUS(XCI))\nSCRIBE(PRIMUS(1))");
+        assert_eq!(output, vec!["VERUM", "FALSUM", "FALSUM"]);
+    }
+
+    #[test]
+    fn test_par_impar() {
+        let output = run("SCRIBE(PAR(XLII))\nSCRIBE(PAR(XV))\nSCRIBE(PAR(0))\nSCRIBE(PAR(0 SUBTRAHE 4))");
+        assert_eq!(output, vec!["VERUM", "FALSUM", "VERUM", "VERUM"]);
+        let output = run("SCRIBE(IMPAR(XLII))\nSCRIBE(IMPAR(XV))\nSCRIBE(IMPAR(0 SUBTRAHE 3))");
+        assert_eq!(output, vec!["FALSUM", "VERUM", "VERUM"]);
+
+        for input in [r#"SCRIBE(PAR("II"))"#, "SCRIBE(IMPAR(VERUM))"] {
+            let program = Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap();
+            let result = Interpreter::new().run(&program);
+            assert!(matches!(result, Err(NumerusError::TypeMismatch { .. })), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_factores() {
+        let (_, env) = run_and_get_env("DECLARA X EST FACTORES(CCCLX)");
+        let expected = [2, 2, 2, 3, 3, 5].into_iter().map(Value::Number).collect();
+        assert_eq!(env.get("X").unwrap(), Value::List(expected));
+
+        let output = run("SCRIBE(FACTORES(CCCLX))\nSCRIBE(FACTORES(1))");
+        assert_eq!(output, vec!["{II, II, II, III, III, V}", "{}"]);
+    }
+
+    #[test]
+    fn test_primes_near_i32_max() {
+        assert!(is_prime(i32::MAX));
+        assert!(!is_prime(i32::MAX - 1));
+        assert_eq!(prime_factors(i32::MAX), vec![i32::MAX]);
+        assert_eq!(prime_factors(i32::MAX - 1), vec![2, 3, 3, 7, 11, 31, 151, 331]);
+
+        let mut lexer = Lexer::new("SCRIBE(PRIMUS(2147483647))\nSCRIBE(FACTORES(2147483647))");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert_eq!(interpreter.run(&program).unwrap(), vec!["VERUM", "{2147483647}"]);
+    }
+
+    #[test]
+    fn test_primus_rejects_invalid_arguments() {
+        for input in ["DECLARA X EST PRIMUS(0)", "DECLARA X EST FACTORES(2 SUBTRAHE 5)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(matches!(result, Err(NumerusError::InvalidFunctionArgument { .. })));
+        }
+
+        let mut lexer = Lexer::new(r#"DECLARA X EST PRIMUS("VII")"#);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_monstra_tabulam() {
+        let output = run("MONSTRA_TABULAM(1, III)");
+        assert_eq!(output.first().unwrap(), "  1 = I");
+        assert_eq!(output.last().unwrap(), "  3 = III");
+        assert_eq!(output.len(), 3);
+
+        let output = run("MONSTRA_TABULAM(MCMXCIX, MM)");
+        assert_eq!(output, vec!["1999 = MCMXCIX", "2000 = MM"]);
+    }
+
+    #[test]
+    fn test_monstra_tabulam_invalid_range() {
+        for input in ["MONSTRA_TABULAM(0, III)", "MONSTRA_TABULAM(XX, XV)", "MONSTRA_TABULAM(1, MMM ADDIUS MMM)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(matches!(result, Err(NumerusError::InvalidFunctionArgument { .. })), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_roman_overflow_on_print_has_span() {
+        let source = "SCRIBE(MMM MULTIPLICA MM)";
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.run(&program);
+        match result {
+            Err(NumerusError::NumberOutOfRange { value: 6_000_000, span: Some(span) }) => {
+                assert_eq!(&source[span.start..span.end], "MMM MULTIPLICA MM");
+            }
+            other => panic!("expected a spanned NumberOutOfRange, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_progressio() {
+        let numbers = |values: &[i32]| Value::List(values.iter().copied().map(Value::Number).collect());
+
+        let (_, env) = run_and_get_env(r#"
+DECLARA A EST PROGRESSIO(1, 5, 1)
+DECLARA B EST PROGRESSIO(II, XX, III)
+DECLARA C EST PROGRESSIO(10, 4, 0 SUBTRAHE 3)
+DECLARA D EST PROGRESSIO(VII, VII, 1)
+"#);
+        assert_eq!(env.get("A").unwrap(), numbers(&[1, 2, 3, 4, 5]));
+        assert_eq!(env.get("B").unwrap(), numbers(&[2, 5, 8, 11, 14, 17, 20]));
+        assert_eq!(env.get("C").unwrap(), numbers(&[10, 7, 4]));
+        assert_eq!(env.get("D").unwrap(), numbers(&[7]));
+    }
+
+    #[test]
+    fn test_progressio_invalid_step() {
+        for input in ["DECLARA A EST PROGRESSIO(1, 5, 0)", "DECLARA A EST PROGRESSIO(5, 1, 1)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(matches!(result, Err(NumerusError::InvalidFunctionArgument { .. })), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_progressio_length_cap() {
+        let program = Parser::new(Lexer::new("DECLARA A EST PROGRESSIO(1, 100, 1)").tokenize().unwrap())
+            .parse()
+            .unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_max_list_length(100);
+        assert!(interpreter.run(&program).is_ok());
+        interpreter.set_max_list_length(99);
+        assert!(matches!(interpreter.run(&program), Err(NumerusError::ListTooLong { len: 100, max: 99 })));
+
+        // The default cap applies before any of the list is built
+        let mut lexer = Lexer::new("DECLARA A EST PROGRESSIO(2147483647, -2147483648, -1)");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+        assert!(matches!(
+            Interpreter::new().run(&program),
+            Err(NumerusError::ListTooLong { len: 4_294_967_296, max: DEFAULT_MAX_LIST_LENGTH })
+        ));
+    }
+
+    #[test]
+    fn test_declare_external() {
+        let mut lexer = Lexer::new("SCRIBE(X)\nX EST X ADDIUS 1\nSCRIBE(X)");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.declare_external("X", Value::Number(41));
+        let output = interpreter.run(&program).unwrap();
+        assert_eq!(output, vec!["XLI", "XLII"]);
+    }
+
+    #[test]
+    fn test_declare_external_cannot_be_redeclared() {
+        let mut lexer = Lexer::new("DECLARA X EST XX");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let program = parser.parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.declare_external("X", Value::Number(1));
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::VariableAlreadyDeclared { .. })));
+    }
+
+    #[test]
+    fn test_uncia() {
+        let output = run("SCRIBE(UNCIA(1))\nSCRIBE(UNCIA(VI))\nSCRIBE(UNCIA(XII))\nSCRIBE(UNCIA(IX))");
+        assert_eq!(output, vec!["UNCIA", "SEMIS", "AS", "DODRANS"]);
+    }
+
+    #[test]
+    fn test_uncia_out_of_range() {
+        for input in ["DECLARA A EST UNCIA(0)", "DECLARA A EST UNCIA(XIII)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(matches!(result, Err(NumerusError::InvalidFunctionArgument { .. })), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_run_is_stateful() {
+        let mut lexer = Lexer::new("DECLARA X EST XLII\nSCRIBE(X)");
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.run(&program).unwrap();
+        let result = interpreter.run(&program);
+        assert!(matches!(result, Err(NumerusError::VariableAlreadyDeclared { .. })));
+    }
+
+    #[test]
+    fn test_run_fresh() {
+        let mut lexer = Lexer::new("DECLARA X EST XLII\nSCRIBE(X)");
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_step_limit(100);
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["XLII"]);
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["XLII"]);
+
+        interpreter.reset();
+        assert!(!interpreter.environment().contains("X"));
+    }
+
+    #[test]
+    fn test_sorta_numbers() {
+        let output = run("SCRIBE(SORTA({XX, II, 7, XIV, II}))\nSCRIBE(SORTA({XX, II, 7}, VERUM))");
+        assert_eq!(output, vec!["{II, II, VII, XIV, XX}", "{XX, VII, II}"]);
+    }
+
+    #[test]
+    fn test_sorta_strings() {
+        let (_, env) = run_and_get_env(r#"DECLARA A EST SORTA({"gamma", "alpha", "beta"}, FALSUM)"#);
+        let expected = ["alpha", "beta", "gamma"].iter().map(|s| Value::String(s.to_string())).collect();
+        assert_eq!(env.get("A").unwrap(), Value::List(expected));
+    }
+
+    #[test]
+    fn test_sorta_rejects_mixed_list() {
+        for input in [r#"DECLARA A EST SORTA({II, "I"})"#, "DECLARA A EST SORTA({VERUM, FALSUM})", "DECLARA A EST SORTA(XII)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(matches!(result, Err(NumerusError::TypeMismatch { .. })), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_adde() {
+        let output = run("DECLARA A EST {II}\nA EST ADDE(A, III)\nA EST ADDE(A, \"IV\")\nSCRIBE(A)");
+        assert_eq!(output, vec!["{II, III, IV}"]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let (_, env) = run_and_get_env("DECLARA A EST REMOVE({XV, XX, XXX}, II)");
+        assert_eq!(env.get("A").unwrap(), Value::List(vec![Value::Number(15), Value::Number(30)]));
+    }
+
+    #[test]
+    fn test_remove_out_of_range() {
+        for (input, bad_index) in [("DECLARA A EST REMOVE({XV, XX}, III)", 3), ("DECLARA A EST REMOVE({XV, XX}, 0)", 0)] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            let program = parser.parse().unwrap();
+            let mut interpreter = Interpreter::new();
+            let result = interpreter.run(&program);
+            assert!(
+                matches!(result, Err(NumerusError::IndexOutOfBounds { index, len: 2, .. }) if index == bad_index),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_boolean_concatenation() {
+        let output = run(r#"SCRIBE("VII primus: " ADDIUS (PRIMUS(VII)))
+SCRIBE(PRIMUS(XV) ADDIUS " pro XV")"#);
+        assert_eq!(output, vec!["VII primus: VERUM", "FALSUM pro XV"]);
+    }
+
+    #[test]
+    fn test_ex_ambitu_uses_provider() {
+        let program = Parser::new(Lexer::new(r#"SCRIBE(EX_AMBITU("PATRIA") ADDIUS "!")
+SCRIBE(EX_AMBITU("NEMO"))"#).tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_env_provider(Box::new(|name| (name == "PATRIA").then(|| "ROMA".to_string())));
+        assert!(matches!(
+            interpreter.run_fresh(&program),
+            Err(NumerusError::InvalidFunctionArgument { ref name, .. }) if name == "EX_AMBITU"
+        ));
+        assert_eq!(interpreter.output, vec!["ROMA!"]);
+
+        interpreter.set_unset_env_is_empty(true);
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["ROMA!", ""]);
+    }
+
+    #[test]
+    fn test_digit_grouping() {
+        let program = Parser::new(Lexer::new("SCRIBE(ARABIZA(MMMCMXCIX))\nSCRIBE(ARABIZA(XLII))\nSCRIBE(ARABIZA(0 SUBTRAHE MM))")
+            .tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["3999", "42", "-2000"]);
+
+        interpreter.set_digit_grouping(Some('.'));
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["3.999", "42", "-2.000"]);
+
+        interpreter.set_digit_grouping(Some(','));
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["3,999", "42", "-2,000"]);
+    }
+
+    #[test]
+    fn test_digit_grouping_in_table() {
+        let program = Parser::new(Lexer::new("MONSTRA_TABULAM(999, 1000)").tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_digit_grouping(Some(','));
+        assert_eq!(interpreter.run_fresh(&program).unwrap(), vec!["  999 = CMXCIX", "1,000 = M"]);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(run(r#"SCRIBE(ARABIZA(HEX("FF")))
+SCRIBE(HEX("ff") ADDIUS II)"#), vec!["255", "CCLVII"]);
+
+        let errors: Vec<_> = [
+            r#"SCRIBE(HEX("FG"))"#,
+            r#"SCRIBE(HEX(""))"#,
+            r#"SCRIBE(HEX("1000"))"#,
+            r#"SCRIBE(HEX("0"))"#,
+            r#"SCRIBE(HEX("FFFFFFFFFFFFFFFFFFFF"))"#,
+        ]
+            .iter()
+            .map(|input| {
+                let program = Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap();
+                Interpreter::new().run(&program).unwrap_err()
+            })
+            .collect();
+        assert!(matches!(&errors[0], NumerusError::InvalidFunctionArgument { name, .. } if name == "HEX"));
+        assert!(matches!(&errors[1], NumerusError::InvalidFunctionArgument { .. }));
+        assert!(matches!(&errors[2], NumerusError::HexOutOfRange { digits, .. } if digits == "1000"));
+        assert!(matches!(&errors[3], NumerusError::HexOutOfRange { digits, .. } if digits == "0"));
+        assert_eq!(
+            errors[4].to_string(),
+            "ERRATUM: HEX(\"FFFFFFFFFFFFFFFFFFFF\") extra fines est! (1–3999 / I–MMMCMXCIX)"
+        );
+    }
+
+    #[test]
+    fn test_hex_arabic_only() {
+        let source = "SCRIBE(HEX(\"1000\"))\nSCRIBE(HEX(\"0\"))\nSCRIBE(HEX(\"7FFFFFFF\"))\nSCRIBE(HEX(\"80000000\"))";
+        let mut lexer = Lexer::new(source);
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        let (output, errors) = interpreter.run_lenient(&program);
+        assert_eq!(output, vec!["4096", "0", "2147483647"]);
+        assert!(matches!(
+            errors.as_slice(),
+            [NumerusError::HexOutOfRange { digits, range: "0–7FFFFFFF", .. }] if digits == "80000000"
+        ));
+    }
+
+    #[test]
+    fn test_verte() {
+        let output = run(r#"SCRIBE(VERTE("Cæsar"))
+SCRIBE(VERTE({XV, XX, XXX}))
+SCRIBE(VERTE(""))"#);
+        assert_eq!(output, vec!["rasæC", "{XXX, XX, XV}", ""]);
+
+        let program = Parser::new(Lexer::new("SCRIBE(VERTE(XII))").tokenize().unwrap()).parse().unwrap();
+        assert!(matches!(
+            Interpreter::new().run(&program),
+            Err(NumerusError::TypeMismatch { ref operation, .. }) if operation == "VERTE"
+        ));
+    }
+
+    #[test]
+    fn test_adjacent_strings_output() {
+        let output = run("SCRIBE(\"Salve, \" \"Roma\"\n    \"!\")\nSCRIBE(\"{} et \" \"{}\", XV, XX)");
+        assert_eq!(output, vec!["Salve, Roma!", "XV et XX"]);
+    }
+
+    #[test]
+    fn test_divide_cum_residuo() {
+        let (_, env) = run_and_get_env("DECLARA Q EST DIVIDE_CUM_RESIDUO(VII, II)\nDECLARA R EST DIVIDE_CUM_RESIDUO(XX, XX)");
+        assert_eq!(env.get("Q").unwrap(), Value::List(vec![Value::Number(3), Value::Number(1)]));
+        assert_eq!(env.get("R").unwrap(), Value::List(vec![Value::Number(1), Value::Number(0)]));
+
+        let program = Parser::new(Lexer::new("SCRIBE(DIVIDE_CUM_RESIDUO(VII, 0))").tokenize().unwrap()).parse().unwrap();
+        assert!(matches!(Interpreter::new().run(&program), Err(NumerusError::DivisionByZero { .. })));
+    }
+
+    #[test]
+    fn test_duplica_dimidium() {
+        let output = run("SCRIBE(DUPLICA(XXI))\nSCRIBE(DIMIDIUM(XLV))\nSCRIBE(ARABIZA(DUPLICA(MM)))");
+        assert_eq!(output, vec!["XLII", "XXII", "4000"]);
+
+        let failure = |input: &str| {
+            let program = Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap();
+            Interpreter::new().run(&program).unwrap_err()
+        };
+        // 4000 is a fine number, it just cannot be written in Roman numerals
+        assert!(matches!(failure("SCRIBE(DUPLICA(MM))"), NumerusError::NumberOutOfRange { value: 4000, .. }));
+        assert!(matches!(
+            failure("DECLARA A EST MMM MULTIPLICA MMM MULTIPLICA CC\nA EST DUPLICA(A)"),
+            NumerusError::IntegerOverflow { value: 3_600_000_000, .. }
+        ));
+        assert!(matches!(failure("SCRIBE(DIMIDIUM(\"XX\"))"), NumerusError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn test_overflow_points_at_statement() {
+        let source = "DECLARA TOTAL EST MMM MULTIPLICA MMM\nTOTAL EST TOTAL MULTIPLICA CC\nTOTAL EST TOTAL ADDIUS TOTAL";
+        let program = Parser::new(Lexer::new(source).tokenize().unwrap()).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+        let error = interpreter.run(&program).unwrap_err();
+
+        assert!(matches!(error, NumerusError::IntegerOverflow { value: 3_600_000_000, .. }));
+        assert_eq!(error.span(), Some(program.statements[2].span()));
+        assert_eq!(interpreter.failed_statement(), Some(program.statements[2].span()));
+        assert!(format_error_with_context(source, &error).contains("TOTAL EST TOTAL ADDIUS TOTAL"));
+    }
+
+    #[test]
+    fn test_run_lenient_continues_after_errors() {
+        let program = Parser::new(Lexer::new("SCRIBE(\"ante\")\nSCRIBE(XX DIVIDE 0)\nSCRIBE(\"post\")")
+            .tokenize().unwrap()).parse().unwrap();
+
+        let (output, errors) = Interpreter::new().run_lenient(&program);
+        assert_eq!(output, vec!["ante", "post"]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], NumerusError::DivisionByZero { .. }));
+    }
+
+    #[test]
+    fn test_run_lenient_with_reports_errors_in_order() {
+        let program = Parser::new(Lexer::new("SCRIBE(\"ante\")\nSCRIBE(XX DIVIDE 0)\nSCRIBE(\"post\")")
+            .tokenize().unwrap()).parse().unwrap();
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut interpreter = Interpreter::new();
+        let printed = Rc::clone(&log);
+        interpreter.set_on_print(Box::new(move |line| printed.borrow_mut().push(line.to_string())));
+        let (_, errors) = interpreter.run_lenient_with(&program, |error, statement| {
+            assert_eq!(statement, program.statements[1].span());
+            log.borrow_mut().push(format!("ERRATUM {:?}", error.span().map(|span| span.line)));
+        });
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(*log.borrow(), vec!["ante", "ERRATUM Some(2)", "post"]);
+    }
+
+    #[test]
+    fn test_romaniza_minuscula() {
+        let output = run("SCRIBE(\"pagina \" ADDIUS ROMANIZA_MINUSCULA(42))\nSCRIBE(ROMANIZA(42))");
+        assert_eq!(output, vec!["pagina xlii", "XLII"]);
+    }
+
+    #[test]
+    fn test_summa_usque() {
+        let output = run("SCRIBE(SUMMA_USQUE(XX))\nSCRIBE(SUMMA_INTER(XV, XX))\nSCRIBE(ARABIZA(SUMMA_USQUE(XC)))");
+        assert_eq!(output, vec!["CCX", "CV", "4095"]);
+
+        let failure = |input: &str| {
+            let program = Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap();
+            Interpreter::new().run(&program).unwrap_err()
+        };
+        // The sum itself is fine; only writing it in Roman numerals fails
+        assert!(matches!(failure("SCRIBE(SUMMA_USQUE(XC))"), NumerusError::NumberOutOfRange { value: 4095, .. }));
+        assert!(matches!(
+            failure("SCRIBE(SUMMA_USQUE(MMM MULTIPLICA CC))"),
+            NumerusError::IntegerOverflow { value: 180_000_300_000, .. }
+        ));
+        assert!(matches!(failure("SCRIBE(SUMMA_INTER(XX, XV))"), NumerusError::InvalidFunctionArgument { .. }));
+    }
+
+    #[test]
+    fn test_arabic_only_output() {
+        let source = "DECLARA XIV EST 4000\nSCRIBE(XIV)\nSCRIBE(\"Summa: \" ADDIUS XIV ADDIUS 2)\nSCRIBE({0, XIV})\nSCRIBE(ROMANIZA(42))";
+        let mut lexer = Lexer::new(source);
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert_eq!(interpreter.run(&program).unwrap(), vec!["4000", "Summa: 40002", "{0, 4000}", "XLII"]);
+    }
+
+    #[test]
+    fn test_arabic_only_divide_overflow() {
+        let mut lexer = Lexer::new("SCRIBE(-2147483648 DIVIDE -1)\nSCRIBE(-2147483648 DIVIDE -2)");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        interpreter.set_warn_on_truncation(true);
+        let error = interpreter.run(&program).unwrap_err();
+        assert!(matches!(error, NumerusError::IntegerOverflow { value: 2_147_483_648, .. }));
+        assert_eq!(error.span(), Some(program.statements[0].span()));
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert_eq!(interpreter.run_lenient(&program).0, vec!["1073741824"]);
+    }
+
+    #[test]
+    fn test_arabic_only_negative_literal() {
+        let mut lexer = Lexer::new("DECLARA X EST -5\nSCRIBE(X)\nSCRIBE(X ADDIUS 8)");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let program = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert_eq!(interpreter.run(&program).unwrap(), vec!["-5", "3"]);
+    }
+
+    #[test]
+    fn test_inspice_passes_value_through() {
+        let (output, env) = run_and_get_env("DECLARA Z EST XX\nDECLARA Y EST XV ADDIUS INSPICE(Z MULTIPLICA II)\nSCRIBE(Y)");
+        assert_eq!(output, vec!["[inspectio] VALOR = XL", "LV"]);
+        assert_eq!(env.get("Y").unwrap(), Value::Number(55));
+    }
+
+    #[test]
+    fn test_exprime_reads_roman_or_arabic() {
+        let (_, env) = run_and_get_env("DECLARA A EST EXPRIME(\"XLII\")\nDECLARA B EST EXPRIME(\" 42 \")\nDECLARA C EST EXPRIME(XLII)");
+        assert_eq!(env.get("A").unwrap(), Value::Number(42));
+        assert_eq!(env.get("B").unwrap(), Value::Number(42));
+        assert_eq!(env.get("C").unwrap(), Value::Number(42));
+
+        let failure = |input: &str| {
+            let program = Parser::new(Lexer::new(input).tokenize().unwrap()).parse().unwrap();
+            Interpreter::new().run(&program).unwrap_err()
+        };
+        assert!(matches!(failure("SCRIBE(EXPRIME(\"hello\"))"), NumerusError::InvalidFunctionArgument { .. }));
+        assert!(matches!(failure("SCRIBE(EXPRIME(\"VX\"))"), NumerusError::InvalidRomanForm { span: Some(_), .. }));
+        assert!(matches!(failure("SCRIBE(EXPRIME(VERUM))"), NumerusError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn test_typus_names_each_type() {
+        let output = run("SCRIBE(TYPUS(XLII))\nSCRIBE(TYPUS(\"salve\"))\nSCRIBE(TYPUS(VERUM))\nSCRIBE(TYPUS(DIVIDE_CUM_RESIDUO(VII, II)))");
+        assert_eq!(output, vec!["NUMERUS", "CHORDA", "VERITAS", "COPIA"]);
+
+        let (_, env) = run_and_get_env("DECLARA T EST TYPUS(TYPUS(XI))");
+        assert_eq!(env.get("T").unwrap(), Value::String("CHORDA".to_string()));
+    }
 }
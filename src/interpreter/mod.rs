@@ -2,4 +2,4 @@ mod environment;
 mod evaluator;
 
 pub use environment::{Environment, Value};
-pub use evaluator::Interpreter;
+pub use evaluator::{EnvProvider, Interpreter, PrintCallback, Warning};
@@ -1,35 +1,175 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
 use super::{Span, Token, TokenKind};
 use crate::error::NumerusError;
-use crate::roman::{from_roman, looks_like_roman};
+use crate::roman::{from_roman, from_roman_lenient, looks_like_roman, NumeralMode, MAX_ROMAN};
+
+/// Default maximum length of an identifier, keyword or Roman numeral
+pub const DEFAULT_MAX_IDENTIFIER_LENGTH: usize = 256;
+
+/// Reserved words and the tokens they lex to
+static KEYWORDS: [(&str, TokenKind); 38] = [
+    ("DECLARA", TokenKind::Declara),
+    ("EST", TokenKind::Est),
+    ("ADDIUS", TokenKind::Addius),
+    ("SUBTRAHE", TokenKind::Subtrahe),
+    ("MULTIPLICA", TokenKind::Multiplica),
+    ("DIVIDE", TokenKind::Divide),
+    ("SCRIBE", TokenKind::Scribe),
+    ("SCRIBE_ERRATUM", TokenKind::ScribeErratum),
+    ("AVTEM", TokenKind::Avtem),
+    ("MONSTRA_TABULAM", TokenKind::MonstraTabulam),
+    ("ROMANIZA", TokenKind::Romaniza),
+    ("ARABIZA", TokenKind::Arabiza),
+    ("EXPRIME", TokenKind::Exprime),
+    ("COMPARA", TokenKind::Compara),
+    ("PRIMUS", TokenKind::Primus),
+    ("FACTORES", TokenKind::Factores),
+    ("PROGRESSIO", TokenKind::Progressio),
+    ("UNCIA", TokenKind::Uncia),
+    ("SORTA", TokenKind::Sorta),
+    ("ADDE", TokenKind::Adde),
+    ("REMOVE", TokenKind::Remove),
+    ("EX_AMBITU", TokenKind::ExAmbitu),
+    ("HEX", TokenKind::Hex),
+    ("VERTE", TokenKind::Verte),
+    ("DIVIDE_CUM_RESIDUO", TokenKind::DivideCumResiduo),
+    ("DUPLICA", TokenKind::Duplica),
+    ("DIMIDIUM", TokenKind::Dimidium),
+    ("ROMANIZA_MINUSCULA", TokenKind::RomanizaMinuscula),
+    ("SUMMA_USQUE", TokenKind::SummaUsque),
+    ("SUMMA_INTER", TokenKind::SummaInter),
+    ("INSPICE", TokenKind::Inspice),
+    ("TYPUS", TokenKind::Typus),
+    ("PAR", TokenKind::Par),
+    ("IMPAR", TokenKind::Impar),
+    ("VERUM", TokenKind::Verum),
+    ("FALSUM", TokenKind::Falsum),
+    ("NUMERUS", TokenKind::Numerus),
+    ("CHORDA", TokenKind::Chorda),
+];
+
+/// The reserved words alone, in the same order as `KEYWORDS`
+static KEYWORD_NAMES: [&str; KEYWORDS.len()] = {
+    let mut names = [""; KEYWORDS.len()];
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = KEYWORDS[i].0;
+        i += 1;
+    }
+    names
+};
+
+/// Whether `s` is a reserved word (and so cannot name a variable)
+pub fn is_keyword(s: &str) -> bool {
+    keyword_kind(s).is_some()
+}
+
+/// Every reserved word the lexer recognises
+pub fn keywords() -> &'static [&'static str] {
+    &KEYWORD_NAMES
+}
+
+/// The token a reserved word lexes to, if `s` is one
+fn keyword_kind(s: &str) -> Option<TokenKind> {
+    KEYWORDS.iter().find(|(name, _)| *name == s).map(|(_, kind)| kind.clone())
+}
+
+/// Tokenize `source` with comments kept and group the tokens by the line they start on,
+/// so an editor can highlight a range of lines without re-lexing each one.
+/// Lines with no tokens are absent from the map and the end-of-file token is omitted.
+pub fn tokens_by_line(source: &str) -> Result<BTreeMap<usize, Vec<Token>>, NumerusError> {
+    let mut lexer = Lexer::new(source);
+    lexer.set_keep_comments(true);
+
+    let mut lines: BTreeMap<usize, Vec<Token>> = BTreeMap::new();
+    for token in lexer.tokenize()? {
+        if token.kind != TokenKind::Eof {
+            lines.entry(token.span.line).or_default().push(token);
+        }
+    }
+    Ok(lines)
+}
 
 pub struct Lexer<'a> {
-    #[allow(dead_code)]
-    input: &'a str,
-    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    input: Cow<'a, str>,
+    /// Byte offset of the next character; doubles as the read cursor
     current_pos: usize,
     line: usize,
     column: usize,
+    lenient_numerals: bool,
+    numeral_mode: NumeralMode,
+    keep_comments: bool,
+    max_identifier_length: usize,
+}
+
+impl Lexer<'static> {
+    /// Build a lexer that owns its source, so it can outlive the buffer it
+    /// was read from (e.g. a line handed over by a streaming reader)
+    pub fn from_string(input: String) -> Self {
+        Self::from_cow(Cow::Owned(input))
+    }
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
+        Self::from_cow(Cow::Borrowed(input))
+    }
+
+    fn from_cow(input: Cow<'a, str>) -> Self {
         Self {
             input,
-            chars: input.char_indices().peekable(),
             current_pos: 0,
             line: 1,
             column: 1,
+            lenient_numerals: false,
+            numeral_mode: NumeralMode::Roman,
+            keep_comments: false,
+            max_identifier_length: DEFAULT_MAX_IDENTIFIER_LENGTH,
         }
     }
 
+    /// Peek at the character under the cursor without consuming it
+    fn peek(&self) -> Option<(usize, char)> {
+        self.input[self.current_pos..]
+            .chars()
+            .next()
+            .map(|ch| (self.current_pos, ch))
+    }
+
+    /// Accept additive Roman spellings such as `IIII` alongside canonical numerals
+    pub fn set_lenient_numerals(&mut self, lenient: bool) {
+        self.lenient_numerals = lenient;
+    }
+
+    /// Switch to `NumeralMode::ArabicOnly` to lex any i32 literal and treat
+    /// Roman-looking words as identifiers
+    pub fn set_numeral_mode(&mut self, mode: NumeralMode) {
+        self.numeral_mode = mode;
+    }
+
+    /// Keep `NOTA:` comment tokens in the output of `tokenize` (dropped by default),
+    /// so the parser produces `Statement::Comment` nodes for documentation tools
+    pub fn set_keep_comments(&mut self, keep: bool) {
+        self.keep_comments = keep;
+    }
+
+    /// Set the maximum length of an identifier, keyword or Roman numeral;
+    /// longer words fail fast with `IdentifierTooLong`
+    pub fn set_max_identifier_length(&mut self, max: usize) {
+        self.max_identifier_length = max;
+    }
+
     /// Tokenize the entire input
     pub fn tokenize(&mut self) -> Result<Vec<Token>, NumerusError> {
         let mut tokens = Vec::new();
 
         while let Some(token) = self.next_token()? {
-            // Skip newlines and comments for now (could keep them for formatting)
+            // Skip newlines, and comments unless asked to keep them
             match &token.kind {
-                TokenKind::Newline | TokenKind::Comment(_) => continue,
+                TokenKind::Newline => continue,
+                TokenKind::Comment(_) if !self.keep_comments => continue,
                 _ => tokens.push(token),
             }
         }
@@ -47,7 +187,7 @@ impl<'a> Lexer<'a> {
     fn next_token(&mut self) -> Result<Option<Token>, NumerusError> {
         self.skip_whitespace();
 
-        let Some(&(start, ch)) = self.chars.peek() else {
+        let Some((start, ch)) = self.peek() else {
             return Ok(None);
         };
 
@@ -57,6 +197,7 @@ impl<'a> Lexer<'a> {
             '{' => self.single_char_token(TokenKind::LeftBrace),
             '}' => self.single_char_token(TokenKind::RightBrace),
             ',' => self.single_char_token(TokenKind::Comma),
+            ':' => self.single_char_token(TokenKind::Colon),
             '"' => self.read_string(),
             '\n' => {
                 let col = self.column;
@@ -71,6 +212,12 @@ impl<'a> Lexer<'a> {
             }
             'A'..='Z' | 'a'..='z' | '_' => self.read_identifier_or_keyword(),
             '0'..='9' => self.read_arabic_number(),
+            // There is no minus operator, so `-5` can only be a negative literal
+            '-' if self.numeral_mode == NumeralMode::ArabicOnly
+                && self.input[start + 1..].starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                self.read_arabic_number()
+            }
             _ => Err(NumerusError::UnexpectedCharacter {
                 ch,
                 line: self.line,
@@ -81,7 +228,7 @@ impl<'a> Lexer<'a> {
 
     /// Advance to the next character
     fn advance(&mut self) -> Option<(usize, char)> {
-        if let Some((pos, ch)) = self.chars.next() {
+        if let Some((pos, ch)) = self.peek() {
             self.current_pos = pos + ch.len_utf8();
             self.column += 1;
             Some((pos, ch))
@@ -92,7 +239,7 @@ impl<'a> Lexer<'a> {
 
     /// Skip whitespace (except newlines)
     fn skip_whitespace(&mut self) {
-        while let Some(&(_, ch)) = self.chars.peek() {
+        while let Some((_, ch)) = self.peek() {
             if ch == ' ' || ch == '\t' || ch == '\r' {
                 self.advance();
             } else {
@@ -119,8 +266,15 @@ impl<'a> Lexer<'a> {
         let start_column = self.column;
         let mut lexeme = String::new();
 
-        while let Some(&(_, ch)) = self.chars.peek() {
+        while let Some((_, ch)) = self.peek() {
             if ch.is_ascii_alphanumeric() || ch == '_' {
+                if lexeme.len() == self.max_identifier_length {
+                    return Err(NumerusError::IdentifierTooLong {
+                        max: self.max_identifier_length,
+                        line: self.line,
+                        column: start_column,
+                    });
+                }
                 lexeme.push(ch);
                 self.advance();
             } else {
@@ -130,35 +284,39 @@ impl<'a> Lexer<'a> {
 
         let span = Span::new(start, self.current_pos, self.line, start_column);
 
+        // A run of only Roman letters and digits (X1V) is a garbled numeral,
+        // not an identifier; names like var1 are unaffected
+        if self.numeral_mode == NumeralMode::Roman
+            && lexeme.contains(|c: char| c.is_ascii_digit())
+            && lexeme.chars().all(|c| c.is_ascii_digit() || "IVXLCDM".contains(c))
+        {
+            return Err(NumerusError::MixedNumeralForm { lexeme, span });
+        }
+
         // Check if it's NOTA: (comment)
-        if lexeme == "NOTA" {
-            if self.chars.peek().map(|&(_, c)| c) == Some(':') {
-                self.advance(); // consume ':'
-                return self.read_comment(start, start_column);
-            }
+        if lexeme == "NOTA" && self.peek().map(|(_, c)| c) == Some(':') {
+            self.advance(); // consume ':'
+            return self.read_comment(start, start_column);
         }
 
         // Check for keywords
-        let kind = match lexeme.as_str() {
-            "DECLARA" => TokenKind::Declara,
-            "EST" => TokenKind::Est,
-            "ADDIUS" => TokenKind::Addius,
-            "SUBTRAHE" => TokenKind::Subtrahe,
-            "MULTIPLICA" => TokenKind::Multiplica,
-            "DIVIDE" => TokenKind::Divide,
-            "SCRIBE" => TokenKind::Scribe,
-            "AVTEM" => TokenKind::Avtem,
-            "ROMANIZA" => TokenKind::Romaniza,
-            "ARABIZA" => TokenKind::Arabiza,
-            "EXPRIME" => TokenKind::Exprime,
-            _ => {
+        let kind = match keyword_kind(&lexeme) {
+            Some(kind) => kind,
+            None => {
                 // Check if it's a valid Roman numeral
                 // Only treat as Roman numeral if:
                 // 1. It looks like a Roman numeral (only I, V, X, L, C, D, M)
                 // 2. It's at least 2 characters (single chars are identifiers)
                 // 3. It parses successfully
-                if lexeme.len() >= 2 && looks_like_roman(&lexeme) {
-                    match from_roman(&lexeme) {
+                // A leading underscore (e.g. `_XIV`) never looks like a Roman numeral,
+                // so prefix with `_` to use a Roman-looking name as a variable.
+                if self.numeral_mode == NumeralMode::Roman && lexeme.len() >= 2 && looks_like_roman(&lexeme) {
+                    let parsed = if self.lenient_numerals {
+                        from_roman_lenient(&lexeme)
+                    } else {
+                        from_roman(&lexeme)
+                    };
+                    match parsed {
                         Ok(value) => TokenKind::RomanLiteral(value),
                         Err(_) => {
                             // Not a valid Roman numeral, treat as identifier
@@ -179,7 +337,7 @@ impl<'a> Lexer<'a> {
         let mut comment = String::new();
 
         // Read until end of line
-        while let Some(&(_, ch)) = self.chars.peek() {
+        while let Some((_, ch)) = self.peek() {
             if ch == '\n' {
                 break;
             }
@@ -190,17 +348,22 @@ impl<'a> Lexer<'a> {
         Ok(Some(Token::new(
             TokenKind::Comment(comment.trim().to_string()),
             Span::new(start, self.current_pos, self.line, start_column),
-            format!("NOTA: {}", comment),
+            self.input[start..self.current_pos].to_string(),
         )))
     }
 
-    /// Read an Arabic number literal
+    /// Read an Arabic number literal, with a leading `-` in Arabic-only mode
     fn read_arabic_number(&mut self) -> Result<Option<Token>, NumerusError> {
         let start = self.current_pos;
         let start_column = self.column;
         let mut lexeme = String::new();
 
-        while let Some(&(_, ch)) = self.chars.peek() {
+        if let Some((_, '-')) = self.peek() {
+            lexeme.push('-');
+            self.advance();
+        }
+
+        while let Some((_, ch)) = self.peek() {
             if ch.is_ascii_digit() {
                 lexeme.push(ch);
                 self.advance();
@@ -209,11 +372,61 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        // Reject fractions like `3.14` with a friendlier message than the bare `.`
+        let mut rest = self.input[self.current_pos..].chars();
+        if rest.next() == Some('.') && rest.next().is_some_and(|c| c.is_ascii_digit()) {
+            let mut found = lexeme;
+            found.push('.');
+            self.advance(); // consume '.'
+            while let Some((_, ch)) = self.peek() {
+                if !ch.is_ascii_digit() {
+                    break;
+                }
+                found.push(ch);
+                self.advance();
+            }
+            return Err(NumerusError::FractionalNotSupported {
+                found,
+                line: self.line,
+                column: start_column,
+            });
+        }
+
+        // Reject scientific notation like `1e3` instead of lexing `1` then `e3`
+        let mut rest = self.input[self.current_pos..].chars();
+        if matches!(rest.next(), Some('e' | 'E')) && rest.next().is_some_and(|c| c.is_ascii_digit()) {
+            let mut found = lexeme;
+            while let Some((_, ch)) = self.peek() {
+                if !ch.is_ascii_alphanumeric() {
+                    break;
+                }
+                found.push(ch);
+                self.advance();
+            }
+            return Err(NumerusError::UnsupportedNumberFormat {
+                found,
+                line: self.line,
+                column: start_column,
+            });
+        }
+
         let span = Span::new(start, self.current_pos, self.line, start_column);
 
-        let value: i64 = lexeme.parse().unwrap_or(0);
-        if value > 3999 {
-            return Err(NumerusError::NumberOutOfRange { value, span });
+        // Only digits were read, so parsing fails only when the number overflows
+        let value: i64 = lexeme.parse().map_err(|_| NumerusError::TooManyDigits {
+            digits: lexeme.trim_start_matches('-').len(),
+            range: match self.numeral_mode {
+                NumeralMode::Roman => "1–3999 / I–MMMCMXCIX",
+                NumeralMode::ArabicOnly => "-2147483648–2147483647",
+            },
+            span,
+        })?;
+        if self.numeral_mode == NumeralMode::ArabicOnly {
+            if i32::try_from(value).is_err() {
+                return Err(NumerusError::IntegerOverflow { value, span: Some(span) });
+            }
+        } else if value > i64::from(MAX_ROMAN) {
+            return Err(NumerusError::NumberOutOfRange { value, span: Some(span) });
         }
 
         Ok(Some(Token::new(
@@ -234,15 +447,18 @@ impl<'a> Lexer<'a> {
         let mut content = String::new();
 
         loop {
-            match self.chars.peek() {
-                Some(&(_, '"')) => {
+            match self.peek() {
+                Some((_, '"')) => {
                     self.advance(); // consume closing quote
                     break;
                 }
-                Some(&(_, '\n')) | None => {
+                Some((_, '\n')) | None => {
                     return Err(NumerusError::UnterminatedString { line: start_line });
                 }
-                Some(&(_, ch)) => {
+                Some((_, '\\')) => {
+                    content.push(self.read_escape(start_line)?);
+                }
+                Some((_, ch)) => {
                     content.push(ch);
                     self.advance();
                 }
@@ -250,11 +466,71 @@ impl<'a> Lexer<'a> {
         }
 
         Ok(Some(Token::new(
-            TokenKind::StringLiteral(content.clone()),
+            TokenKind::StringLiteral(content),
             Span::new(start, self.current_pos, self.line, start_column),
-            format!("\"{}\"", content),
+            self.input[start..self.current_pos].to_string(),
         )))
     }
+
+    /// Read an escape sequence inside a string: \n \t \\ \" or \u{XXXX}
+    fn read_escape(&mut self, start_line: usize) -> Result<char, NumerusError> {
+        let column = self.column;
+        self.advance(); // consume '\\'
+
+        let invalid = |sequence: String| NumerusError::InvalidEscape {
+            sequence,
+            line: start_line,
+            column,
+        };
+
+        let ch = match self.peek() {
+            Some((_, '\n')) | None => {
+                return Err(NumerusError::UnterminatedString { line: start_line });
+            }
+            Some((_, ch)) => ch,
+        };
+        self.advance();
+
+        match ch {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            'u' => {
+                let mut sequence = String::from("\\u");
+                if self.peek().map(|(_, c)| c) != Some('{') {
+                    return Err(invalid(sequence));
+                }
+                self.advance();
+                sequence.push('{');
+
+                let mut hex = String::new();
+                while let Some((_, c)) = self.peek() {
+                    if c == '}' || c == '"' || c == '\n' {
+                        break;
+                    }
+                    hex.push(c);
+                    self.advance();
+                }
+                sequence.push_str(&hex);
+
+                if self.peek().map(|(_, c)| c) != Some('}') {
+                    return Err(invalid(sequence));
+                }
+                self.advance();
+                sequence.push('}');
+
+                if hex.is_empty() || hex.len() > 6 {
+                    return Err(invalid(sequence));
+                }
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(sequence))
+            }
+            other => Err(invalid(format!("\\{}", other))),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -298,6 +574,22 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_tabs_separate_tokens_like_spaces() {
+        let tabbed = Lexer::new("DECLARA\tX\tEST\t5").tokenize().unwrap();
+        let spaced = Lexer::new("DECLARA X EST 5").tokenize().unwrap();
+        let kinds = |tokens: &[Token]| tokens.iter().map(|t| t.kind.clone()).collect::<Vec<_>>();
+        assert_eq!(kinds(&tabbed), kinds(&spaced));
+
+        // A tab is one byte and one column, like a space
+        let source = "\tDECLARA\t\tTOTAL EST\tXV";
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        for token in &tokens[..4] {
+            assert_eq!(&source[token.span.start..token.span.end], token.lexeme);
+            assert_eq!(token.span.column, token.span.start + 1);
+        }
+    }
+
     #[test]
     fn test_single_roman_chars_are_identifiers() {
         // Single Roman numeral characters should be identifiers (for variable names)
@@ -314,6 +606,31 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_underscore_prefix_forces_identifier() {
+        let tokens = tokenize("_XIV XIV");
+        assert_eq!(tokens, vec![
+            TokenKind::Identifier("_XIV".to_string()),
+            TokenKind::RomanLiteral(14),
+            TokenKind::Eof,
+        ]);
+    }
+
+    #[test]
+    fn test_lenient_numerals() {
+        let mut lexer = Lexer::new("IIII XXXX");
+        lexer.set_lenient_numerals(true);
+        let tokens: Vec<_> = lexer.tokenize().unwrap().into_iter().map(|t| t.kind).collect();
+        assert_eq!(tokens, vec![
+            TokenKind::RomanLiteral(4),
+            TokenKind::RomanLiteral(40),
+            TokenKind::Eof,
+        ]);
+
+        // Strict mode keeps treating them as identifiers
+        assert_eq!(tokenize("IIII")[0], TokenKind::Identifier("IIII".to_string()));
+    }
+
     #[test]
     fn test_arabic_literals() {
         let tokens = tokenize("0 1 42 3999");
@@ -328,10 +645,10 @@ mod tests {
 
     #[test]
     fn test_identifiers() {
-        let tokens = tokenize("VARIABILIS NUMERUS RES");
+        let tokens = tokenize("VARIABILIS VALOR RES");
         assert_eq!(tokens, vec![
             TokenKind::Identifier("VARIABILIS".to_string()),
-            TokenKind::Identifier("NUMERUS".to_string()),
+            TokenKind::Identifier("VALOR".to_string()),
             TokenKind::Identifier("RES".to_string()),
             TokenKind::Eof,
         ]);
@@ -380,6 +697,26 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_typed_declaration() {
+        let tokens = tokenize("DECLARA X: NUMERUS EST 42 DECLARA S: CHORDA EST \"hi\"");
+        assert_eq!(tokens, vec![
+            TokenKind::Declara,
+            TokenKind::Identifier("X".to_string()),
+            TokenKind::Colon,
+            TokenKind::Numerus,
+            TokenKind::Est,
+            TokenKind::ArabicLiteral(42),
+            TokenKind::Declara,
+            TokenKind::Identifier("S".to_string()),
+            TokenKind::Colon,
+            TokenKind::Chorda,
+            TokenKind::Est,
+            TokenKind::StringLiteral("hi".to_string()),
+            TokenKind::Eof,
+        ]);
+    }
+
     #[test]
     fn test_expression() {
         let tokens = tokenize("A ADDIUS B MULTIPLICA C");
@@ -393,6 +730,81 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(tokenize(""), vec![TokenKind::Eof]);
+        assert_eq!(tokenize("  \n\t\r\n"), vec![TokenKind::Eof]);
+        assert_eq!(tokenize("NOTA: primus\nNOTA: secundus\n"), vec![TokenKind::Eof]);
+    }
+
+    #[test]
+    fn test_from_string_outlives_source() {
+        let mut lexer = {
+            let line = format!("DECLARA TOTAL EST {}", "XLII");
+            Lexer::from_string(line)
+        };
+        let kinds: Vec<_> = lexer.tokenize().unwrap().into_iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Declara,
+                TokenKind::Identifier("TOTAL".to_string()),
+                TokenKind::Est,
+                TokenKind::RomanLiteral(42),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_comments() {
+        let mut lexer = Lexer::new("DECLARA X EST XLII\nNOTA: responsum");
+        lexer.set_keep_comments(true);
+        let tokens: Vec<_> = lexer.tokenize().unwrap().into_iter().map(|t| t.kind).collect();
+        assert_eq!(tokens[4], TokenKind::Comment("responsum".to_string()));
+        assert_eq!(tokens.len(), 6);
+    }
+
+    #[test]
+    fn test_scientific_notation_rejected() {
+        let mut lexer = Lexer::new("DECLARA X EST 1e3");
+        assert!(matches!(
+            lexer.tokenize(),
+            Err(NumerusError::UnsupportedNumberFormat { ref found, line: 1, column: 15 }) if found == "1e3"
+        ));
+    }
+
+    #[test]
+    fn test_fraction_rejected() {
+        let mut lexer = Lexer::new("SCRIBE(3.14)");
+        assert!(matches!(
+            lexer.tokenize(),
+            Err(NumerusError::FractionalNotSupported { ref found, line: 1, column: 8 }) if found == "3.14"
+        ));
+
+        // A dot that is not followed by a digit is still an unknown character
+        let mut lexer = Lexer::new("3.");
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::UnexpectedCharacter { ch: '.', .. })));
+    }
+
+    #[test]
+    fn test_identifier_too_long() {
+        let source = format!("DECLARA {} EST 1", "I".repeat(100_000));
+        let mut lexer = Lexer::new(&source);
+        assert!(matches!(
+            lexer.tokenize(),
+            Err(NumerusError::IdentifierTooLong { max: DEFAULT_MAX_IDENTIFIER_LENGTH, line: 1, column: 9 })
+        ));
+
+        let mut lexer = Lexer::new("DECLARA ALPHABET EST 1");
+        lexer.set_max_identifier_length(8);
+        assert!(lexer.tokenize().is_ok());
+
+        let mut lexer = Lexer::new("DECLARA ALPHABETA EST 1");
+        lexer.set_max_identifier_length(8);
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::IdentifierTooLong { max: 8, .. })));
+    }
+
     #[test]
     fn test_number_out_of_range() {
         let mut lexer = Lexer::new("4000");
@@ -400,10 +812,191 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_number_too_many_digits() {
+        let digits = "123456789012345678901234567890";
+        let result = Lexer::new(&format!("SCRIBE({})", digits)).tokenize();
+        assert!(matches!(
+            result,
+            Err(NumerusError::TooManyDigits { digits: 30, span, .. }) if span.start == 7 && span.end == 37
+        ));
+        assert!(result.unwrap_err().to_string().contains("(1–3999 / I–MMMCMXCIX)"));
+
+        // Arabic-only mode has no Roman range to quote
+        let source = format!("-{}", digits);
+        let mut lexer = Lexer::new(&source);
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let error = lexer.tokenize().unwrap_err();
+        assert!(matches!(error, NumerusError::TooManyDigits { digits: 30, .. }));
+        assert!(error.to_string().contains("(-2147483648–2147483647)"), "{}", error);
+
+        // Leading zeros alone do not overflow
+        assert_eq!(tokenize("000000000000000000000000000042")[0], TokenKind::ArabicLiteral(42));
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let tokens = tokenize(r#""I\tII\n\"III\" \\ \u{00B7}""#);
+        assert_eq!(tokens, vec![
+            TokenKind::StringLiteral("I\tII\n\"III\" \\ ·".to_string()),
+            TokenKind::Eof,
+        ]);
+    }
+
+    #[test]
+    fn test_string_lexeme_is_source_text() {
+        let mut lexer = Lexer::new(r#""SPQR\u{00B7}""#);
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].lexeme, r#""SPQR\u{00B7}""#);
+    }
+
+    #[test]
+    fn test_invalid_unicode_escape() {
+        let mut lexer = Lexer::new(r#""\u{110000}""#);
+        assert!(matches!(
+            lexer.tokenize(),
+            Err(NumerusError::InvalidEscape { ref sequence, line: 1, column: 2 }) if sequence == "\\u{110000}"
+        ));
+
+        let mut lexer = Lexer::new(r#""\u00B7""#);
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::InvalidEscape { .. })));
+
+        let mut lexer = Lexer::new(r#""\q""#);
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::InvalidEscape { .. })));
+    }
+
     #[test]
     fn test_unterminated_string() {
         let mut lexer = Lexer::new("\"hello");
         let result = lexer.tokenize();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_mixed_numeral_form() {
+        let result = Lexer::new("DECLARA A EST X1V").tokenize();
+        assert!(matches!(
+            result,
+            Err(NumerusError::MixedNumeralForm { ref lexeme, span }) if lexeme == "X1V" && span.column == 15
+        ));
+
+        assert_eq!(tokenize("var1 MM2x"), vec![
+            TokenKind::Identifier("var1".to_string()),
+            TokenKind::Identifier("MM2x".to_string()),
+            TokenKind::Eof,
+        ]);
+    }
+
+    #[test]
+    fn test_is_keyword() {
+        assert!(is_keyword("DECLARA"));
+        assert!(is_keyword("EX_AMBITU"));
+        assert!(!is_keyword("FOO"));
+        assert!(!is_keyword("declara"));
+
+        for keyword in keywords() {
+            assert!(is_keyword(keyword));
+            assert_ne!(tokenize(keyword)[0], TokenKind::Identifier(keyword.to_string()), "{}", keyword);
+        }
+    }
+
+    #[test]
+    fn test_comment_body_span_excludes_marker() {
+        let source = "SCRIBE(XV) NOTA:\t  ave · Caesar  \nNOTA:";
+        let mut lexer = Lexer::new(source);
+        lexer.set_keep_comments(true);
+        let tokens = lexer.tokenize().unwrap();
+
+        let body = tokens[4].comment_body_span().unwrap();
+        assert_eq!(&source[tokens[4].span.start..tokens[4].span.end], tokens[4].lexeme);
+        assert_eq!(&source[body.start..body.end], "ave · Caesar");
+        assert_eq!((body.line, body.column), (1, 20));
+
+        let empty = tokens[5].comment_body_span().unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(tokens[0].comment_body_span(), None);
+    }
+
+    #[test]
+    fn test_tokens_by_line() {
+        let lines = tokens_by_line("DECLARA TOTAL EST XV\n\nSCRIBE(TOTAL) NOTA: vide\nTOTAL EST XX").unwrap();
+        let kinds = |line: usize| lines[&line].iter().map(|t| t.kind.clone()).collect::<Vec<_>>();
+
+        assert_eq!(lines.keys().copied().collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert_eq!(kinds(1), vec![
+            TokenKind::Declara,
+            TokenKind::Identifier("TOTAL".to_string()),
+            TokenKind::Est,
+            TokenKind::RomanLiteral(15),
+        ]);
+        assert_eq!(kinds(3), vec![
+            TokenKind::Scribe,
+            TokenKind::LeftParen,
+            TokenKind::Identifier("TOTAL".to_string()),
+            TokenKind::RightParen,
+            TokenKind::Comment("vide".to_string()),
+        ]);
+        assert_eq!(kinds(4), vec![
+            TokenKind::Identifier("TOTAL".to_string()),
+            TokenKind::Est,
+            TokenKind::RomanLiteral(20),
+        ]);
+    }
+
+    #[test]
+    fn test_trailing_comment() {
+        let source = "DECLARA X EST V NOTA: hi";
+        let with_comment = Lexer::new(source).tokenize().unwrap();
+        let kinds: Vec<_> = with_comment.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(kinds, vec![
+            TokenKind::Declara,
+            TokenKind::Identifier("X".to_string()),
+            TokenKind::Est,
+            TokenKind::Identifier("V".to_string()),
+            TokenKind::Eof,
+        ]);
+
+        let without_comment = Lexer::new("DECLARA X EST V").tokenize().unwrap();
+        let spans = |tokens: &[Token]| tokens[..4].iter().map(|t| t.span).collect::<Vec<_>>();
+        assert_eq!(spans(&with_comment), spans(&without_comment));
+    }
+
+    #[test]
+    fn test_arabic_only_mode() {
+        let mut lexer = Lexer::new("4000 XIV X1V 2147483647");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let kinds: Vec<_> = lexer.tokenize().unwrap().into_iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![
+            TokenKind::ArabicLiteral(4000),
+            TokenKind::Identifier("XIV".to_string()),
+            TokenKind::Identifier("X1V".to_string()),
+            TokenKind::ArabicLiteral(i32::MAX),
+            TokenKind::Eof,
+        ]);
+
+        let mut lexer = Lexer::new("2147483648");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::IntegerOverflow { value: 2_147_483_648, .. })));
+    }
+
+    #[test]
+    fn test_negative_literal_in_arabic_only_mode() {
+        let mut lexer = Lexer::new("DECLARA X EST -5 -2147483648");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[3].kind, TokenKind::ArabicLiteral(-5));
+        assert_eq!(tokens[3].lexeme, "-5");
+        assert_eq!((tokens[3].span.start, tokens[3].span.end, tokens[3].span.column), (14, 16, 15));
+        assert_eq!(tokens[4].kind, TokenKind::ArabicLiteral(i32::MIN));
+
+        let mut lexer = Lexer::new("-2147483649");
+        lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+        assert!(matches!(lexer.tokenize(), Err(NumerusError::IntegerOverflow { value: -2_147_483_649, .. })));
+
+        // Roman mode has no negative numbers at all
+        assert!(matches!(
+            Lexer::new("-5").tokenize(),
+            Err(NumerusError::UnexpectedCharacter { ch: '-', .. })
+        ));
+    }
 }
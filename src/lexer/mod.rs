@@ -4,4 +4,4 @@ mod lexer;
 
 pub use span::Span;
 pub use token::{Token, TokenKind};
-pub use lexer::Lexer;
+pub use lexer::{is_keyword, keywords, tokens_by_line, Lexer, DEFAULT_MAX_IDENTIFIER_LENGTH};
@@ -13,15 +13,32 @@ impl Span {
     }
 
     /// Merge two spans into one that covers both
+    /// (line and column are those of whichever span starts first)
     pub fn merge(&self, other: &Span) -> Span {
+        let first = if self.start <= other.start { self } else { other };
         Span {
             start: self.start.min(other.start),
             end: self.end.max(other.end),
-            line: self.line.min(other.line),
-            column: if self.line <= other.line { self.column } else { other.column },
+            line: first.line,
+            column: first.column,
         }
     }
 
+    /// Whether the byte offset `pos` lies within the span (`start..end`)
+    pub fn contains(&self, pos: usize) -> bool {
+        (self.start..self.end).contains(&pos)
+    }
+
+    /// Length of the span in bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no source text
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
     /// Create a span for a single position
     pub fn point(pos: usize, line: usize, column: usize) -> Self {
         Self { start: pos, end: pos + 1, line, column }
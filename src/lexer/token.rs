@@ -1,4 +1,5 @@
 use super::Span;
+use crate::roman::to_roman;
 
 /// Token with its kind and location
 #[derive(Debug, Clone, PartialEq)]
@@ -12,8 +13,49 @@ impl Token {
     pub fn new(kind: TokenKind, span: Span, lexeme: String) -> Self {
         Self { kind, span, lexeme }
     }
+
+    /// Describe the token for error messages: its kind, plus the source text
+    /// when that says more than the kind (e.g. `identificator "foo"`)
+    pub fn describe(&self) -> String {
+        let name = self.kind.name();
+        if self.lexeme.is_empty() || self.lexeme == name {
+            name.to_string()
+        } else if self.lexeme.starts_with('"') {
+            format!("{} {}", name, self.lexeme)
+        } else {
+            format!("{} \"{}\"", name, self.lexeme)
+        }
+    }
+
+    /// For a Roman literal written in a non-canonical form (e.g. `IIII`),
+    /// return the canonical spelling (`IV`); `None` for everything else
+    pub fn canonical_form(&self) -> Option<String> {
+        match self.kind {
+            TokenKind::RomanLiteral(value) => to_roman(value)
+                .ok()
+                .filter(|canonical| *canonical != self.lexeme),
+            _ => None,
+        }
+    }
+
+    /// For a comment, the span of its text alone: after the `NOTA:` marker,
+    /// without surrounding whitespace; `None` for everything else
+    pub fn comment_body_span(&self) -> Option<Span> {
+        let TokenKind::Comment(text) = &self.kind else {
+            return None;
+        };
+        let after_marker = self.lexeme.get(COMMENT_MARKER.len()..)?;
+        let padding = &after_marker[..after_marker.len() - after_marker.trim_start().len()];
+
+        let start = self.span.start + COMMENT_MARKER.len() + padding.len();
+        let column = self.span.column + COMMENT_MARKER.len() + padding.chars().count();
+        Some(Span::new(start, start + text.len(), self.span.line, column))
+    }
 }
 
+/// Marker that opens a comment running to the end of the line
+const COMMENT_MARKER: &str = "NOTA:";
+
 /// All possible token types in Numerus++
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
@@ -27,14 +69,43 @@ pub enum TokenKind {
     Multiplica,     // MULTIPLICA - multiplication
     Divide,         // DIVIDE - division
     Scribe,         // SCRIBE - print
+    ScribeErratum,  // SCRIBE_ERRATUM - print to the error stream
     Avtem,          // AVTEM - ceremonial no-op
+    MonstraTabulam, // MONSTRA_TABULAM - print an Arabic/Roman table
+
+    // ═══════════════════════════════════════════════════════════
+    // TYPES (TYPI)
+    // ═══════════════════════════════════════════════════════════
+    Numerus,        // NUMERUS - number type annotation
+    Chorda,         // CHORDA - string type annotation
 
     // ═══════════════════════════════════════════════════════════
     // BUILT-IN FUNCTIONS (FUNCTIONES)
     // ═══════════════════════════════════════════════════════════
     Romaniza,       // ROMANIZA - convert number to Roman string
     Arabiza,        // ARABIZA - convert to Arabic number
-    Exprime,        // EXPRIME - expression evaluation
+    Exprime,        // EXPRIME - read a number from a string
+    Compara,        // COMPARA - three-way comparison
+    Primus,         // PRIMUS - primality test
+    Factores,       // FACTORES - prime factorization
+    Progressio,     // PROGRESSIO - arithmetic sequence
+    Uncia,          // UNCIA - name of a fraction in twelfths
+    Sorta,          // SORTA - sort a list
+    Adde,           // ADDE - append to a list
+    Remove,         // REMOVE - remove from a list
+    ExAmbitu,       // EX_AMBITU - read an OS environment variable
+    Hex,            // HEX - parse a hexadecimal string
+    Verte,          // VERTE - reverse a string or list
+    DivideCumResiduo, // DIVIDE_CUM_RESIDUO - quotient and remainder
+    Duplica,        // DUPLICA - double a number
+    Dimidium,       // DIMIDIUM - halve a number
+    RomanizaMinuscula, // ROMANIZA_MINUSCULA - convert number to lowercase Roman string
+    SummaUsque,     // SUMMA_USQUE - sum of 1 through n
+    SummaInter,     // SUMMA_INTER - sum of a through b
+    Inspice,        // INSPICE - print a value and pass it through
+    Typus,          // TYPUS - name the type of a value
+    Par,            // PAR - whether a number is even
+    Impar,          // IMPAR - whether a number is odd
 
     // ═══════════════════════════════════════════════════════════
     // LITERALS (LITTERAE)
@@ -42,6 +113,8 @@ pub enum TokenKind {
     ArabicLiteral(i32),     // 0-3999
     RomanLiteral(i32),      // Stored as Arabic internally
     StringLiteral(String),  // "...{VAR}..."
+    Verum,                  // VERUM - boolean true
+    Falsum,                 // FALSUM - boolean false
 
     // ═══════════════════════════════════════════════════════════
     // IDENTIFIERS
@@ -56,6 +129,7 @@ pub enum TokenKind {
     LeftBrace,      // {
     RightBrace,     // }
     Comma,          // ,
+    Colon,          // :
 
     // ═══════════════════════════════════════════════════════════
     // SPECIAL
@@ -95,10 +169,37 @@ impl TokenKind {
             TokenKind::Multiplica => "MULTIPLICA",
             TokenKind::Divide => "DIVIDE",
             TokenKind::Scribe => "SCRIBE",
+            TokenKind::ScribeErratum => "SCRIBE_ERRATUM",
             TokenKind::Avtem => "AVTEM",
+            TokenKind::MonstraTabulam => "MONSTRA_TABULAM",
+            TokenKind::Numerus => "NUMERUS",
+            TokenKind::Chorda => "CHORDA",
             TokenKind::Romaniza => "ROMANIZA",
             TokenKind::Arabiza => "ARABIZA",
             TokenKind::Exprime => "EXPRIME",
+            TokenKind::Compara => "COMPARA",
+            TokenKind::Primus => "PRIMUS",
+            TokenKind::Factores => "FACTORES",
+            TokenKind::Progressio => "PROGRESSIO",
+            TokenKind::Uncia => "UNCIA",
+            TokenKind::Sorta => "SORTA",
+            TokenKind::Adde => "ADDE",
+            TokenKind::Remove => "REMOVE",
+            TokenKind::ExAmbitu => "EX_AMBITU",
+            TokenKind::Hex => "HEX",
+            TokenKind::Verte => "VERTE",
+            TokenKind::DivideCumResiduo => "DIVIDE_CUM_RESIDUO",
+            TokenKind::Duplica => "DUPLICA",
+            TokenKind::Dimidium => "DIMIDIUM",
+            TokenKind::RomanizaMinuscula => "ROMANIZA_MINUSCULA",
+            TokenKind::SummaUsque => "SUMMA_USQUE",
+            TokenKind::SummaInter => "SUMMA_INTER",
+            TokenKind::Inspice => "INSPICE",
+            TokenKind::Typus => "TYPUS",
+            TokenKind::Par => "PAR",
+            TokenKind::Impar => "IMPAR",
+            TokenKind::Verum => "VERUM",
+            TokenKind::Falsum => "FALSUM",
             TokenKind::ArabicLiteral(_) => "numerus Arabicus",
             TokenKind::RomanLiteral(_) => "numerus Romanus",
             TokenKind::StringLiteral(_) => "string",
@@ -108,6 +209,7 @@ impl TokenKind {
             TokenKind::LeftBrace => "{",
             TokenKind::RightBrace => "}",
             TokenKind::Comma => ",",
+            TokenKind::Colon => ":",
             TokenKind::Comment(_) => "NOTA",
             TokenKind::Newline => "linea nova",
             TokenKind::Eof => "finis",
@@ -11,17 +11,74 @@
 //! SCRIBE("Summa: " ADDIUS Z)
 //! ```
 
+// Each module keeps its main type in a same-named file (e.g. `lexer/lexer.rs`)
+#![allow(clippy::module_inception)]
+// Without the default `std` feature only the `roman` module is built
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
 pub mod banner;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
 pub mod error;
+#[cfg(feature = "std")]
+pub mod grammar;
+#[cfg(feature = "std")]
 pub mod interpreter;
+#[cfg(feature = "std")]
 pub mod lexer;
+#[cfg(feature = "std")]
 pub mod parser;
+#[cfg(feature = "std")]
 pub mod repl;
 pub mod roman;
 
 // Re-export commonly used types
+#[cfg(feature = "std")]
 pub use error::NumerusError;
-pub use interpreter::Interpreter;
-pub use lexer::Lexer;
+#[cfg(feature = "std")]
+pub use interpreter::{Interpreter, Value};
+#[cfg(feature = "std")]
+pub use lexer::{is_keyword, keywords, tokens_by_line, Lexer};
+#[cfg(feature = "std")]
 pub use parser::Parser;
 pub use roman::{from_roman, to_roman};
+
+/// Evaluate a single expression such as `XV ADDIUS VII` to a value, against
+/// an empty environment; statements and leftover tokens are an error
+///
+/// The expression is lexed as in a program, so a single Roman letter is a
+/// variable name, not a numeral: `V ADDIUS X` fails with `UndefinedVariable`.
+/// Write `5 ADDIUS 10` (or two-letter numerals) instead.
+#[cfg(feature = "std")]
+pub fn eval_expression(source: &str) -> Result<Value, NumerusError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let expression = Parser::new(tokens).parse_expression_entry()?;
+    Interpreter::new().evaluate(&expression)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_expression() {
+        assert_eq!(eval_expression("VI ADDIUS IX").unwrap(), Value::Number(15));
+        assert_eq!(eval_expression("(XV ADDIUS XV) MULTIPLICA 2\n").unwrap(), Value::Number(60));
+        assert_eq!(eval_expression("\"ave\"").unwrap(), Value::String("ave".to_string()));
+        assert_eq!(eval_expression("\n\nXV\n").unwrap(), Value::Number(15));
+        assert_eq!(eval_expression("\nXV\n").unwrap(), eval_expression("XV").unwrap());
+
+        assert!(eval_expression("XV ADDIUS").is_err());
+        assert!(matches!(eval_expression("XV XV"), Err(NumerusError::UnexpectedToken { .. })));
+        assert!(eval_expression("DECLARA A EST V").is_err());
+        // Single letters are names, and the environment is empty
+        assert!(matches!(eval_expression("V ADDIUS X"), Err(NumerusError::UndefinedVariable { .. })));
+    }
+}
@@ -4,23 +4,59 @@
 //!   numerus              - Start the REPL
 //!   numerus file.npp     - Execute a Numerus++ file
 //!   numerus --check file - Check syntax without executing (JSON output)
+//!   numerus --fix-numerals file
+//!                        - Print the file with Roman numerals in canonical form
+//!   numerus --grammar    - Print keywords, operators and built-ins as JSON
+//!   numerus --roman XLII - Convert a Roman numeral to Arabic (prints 42)
+//!   numerus --arabic 42  - Convert an Arabic number to Roman (prints XLII)
+//!
+//! Options:
+//!   --lenient            - Accept additive Roman numerals such as IIII
+//!   --no-lenient         - Only canonical Roman numerals (the default)
+//!   --time               - Report lexing, parsing and execution time on stderr
+//!   --continue-on-error  - Report runtime errors and carry on with the next statement
+//!   --arabic-only        - No Roman literals or 3999 cap; numbers print in Arabic digits
+//!   --roman-numerals     - Roman literals, numbers print in Roman numerals (the default)
+//!
+//! Defaults for these (and for the REPL prompt and colors) can be kept in a
+//! `.numerusrc` file in the current or home directory; flags override it. An
+//! unreadable or invalid `.numerusrc` is reported and ignored.
 
 use std::env;
 use std::fs;
 use std::process;
+use std::time::{Duration, Instant};
 
 use colored::*;
 
+use numerus::analysis::{
+    find_dead_stores, find_invalid_numerals, find_non_canonical_numerals, fix_non_canonical_numerals,
+};
 use numerus::banner::print_mini_banner;
-use numerus::error::format_error_with_context;
+use numerus::config::Config;
+use numerus::error::{format_error_in_statement, format_error_with_context};
+use numerus::grammar;
 use numerus::interpreter::Interpreter;
-use numerus::lexer::Lexer;
+use numerus::lexer::{Lexer, Span};
 use numerus::parser::Parser;
 use numerus::repl::Repl;
+use numerus::roman::{from_roman, from_roman_lenient, to_roman, NumeralMode};
 use numerus::NumerusError;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    // A broken .numerusrc must not stop --help and friends, so fall back to the defaults
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{} {}", e.to_string().bright_yellow(), "(praeferentiae neglectae sunt)".bright_yellow());
+        Config::default()
+    });
+    if !config.color {
+        colored::control::set_override(false);
+    }
+
+    let time = take_flag(&mut args, "--time");
+    let options = Options::from_args(&mut args, &config);
+    let lenient = options.lenient;
 
     // Check for --check mode
     if args.len() >= 2 && args[1] == "--check" {
@@ -31,14 +67,56 @@ fn main() {
         let filename = &args[2];
         match fs::read_to_string(filename) {
             Ok(source) => {
-                check_program(&source);
+                println!("{}", check_program(&source, &options));
             }
             Err(e) => {
                 // Output file read error as JSON
-                println!(
-                    r#"{{"diagnostics":[{{"line":1,"column":1,"end_line":1,"end_column":1,"severity":"error","message":"Cannot read file: {}"}}]}}"#,
-                    e.to_string().replace('"', "\\\"")
-                );
+                println!("{}", read_error_report(&e));
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for --grammar mode
+    if args.len() == 2 && args[1] == "--grammar" {
+        println!("{}", grammar::describe().to_json());
+        return;
+    }
+
+    // Check for --roman / --arabic conversion mode
+    if args.len() >= 2 && (args[1] == "--roman" || args[1] == "--arabic") {
+        if args.len() != 3 {
+            eprintln!("Usage: numerus {} <numerus>", args[1]);
+            process::exit(1);
+        }
+        match convert_numeral(&args[1], &args[2], lenient) {
+            Ok(converted) => println!("{}", converted),
+            Err(message) => {
+                eprintln!("{}", message.bright_red());
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for --fix-numerals mode
+    if args.len() >= 2 && args[1] == "--fix-numerals" {
+        if args.len() < 3 {
+            eprintln!("Usage: numerus --fix-numerals <file.npp>");
+            process::exit(1);
+        }
+        let filename = &args[2];
+        let fixed = fs::read_to_string(filename)
+            .map_err(|e| format!("{}: Non possum legere file '{}': {}", "ERRATUM".bright_red(), filename, e))
+            .and_then(|source| {
+                fix_non_canonical_numerals(&source)
+                    .map_err(|e| format_error_with_context(&source, &e).bright_red().to_string())
+            });
+        match fixed {
+            Ok(fixed) => print!("{}", fixed),
+            Err(message) => {
+                eprintln!("{}", message);
                 process::exit(1);
             }
         }
@@ -49,7 +127,10 @@ fn main() {
         1 => {
             // REPL mode
             match Repl::new() {
-                Ok(mut repl) => repl.run(),
+                Ok(mut repl) => {
+                    repl.apply_config(&config);
+                    repl.run()
+                }
                 Err(e) => {
                     eprintln!("{}: {}", "ERRATUM".bright_red(), e);
                     process::exit(1);
@@ -75,9 +156,23 @@ fn main() {
             match fs::read_to_string(filename) {
                 Ok(source) => {
                     print_mini_banner();
-                    if let Err(e) = run_program(&source) {
-                        eprintln!("{}", format_error_with_context(&source, &e).bright_red());
-                        process::exit(1);
+                    // Report runtime errors as they happen, between the lines printed around them
+                    let report = |e: &NumerusError, statement: Span| {
+                        eprintln!("{}", format_error_in_statement(&source, e, Some(statement)).bright_red());
+                    };
+                    match run_program(&source, &options, report) {
+                        Ok((timings, errors)) => {
+                            if time {
+                                eprintln!("{}", timings);
+                            }
+                            if !errors.is_empty() {
+                                process::exit(1);
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("{}", format_error_with_context(&source, &e).bright_red());
+                            process::exit(1);
+                        }
                     }
                 }
                 Err(e) => {
@@ -98,42 +193,183 @@ fn main() {
     }
 }
 
-/// Run a complete Numerus++ program
-fn run_program(source: &str) -> Result<(), numerus::NumerusError> {
+/// Remove a boolean flag from the argument list, returning whether it was present
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// How to lex and run a program, from the command-line flags
+#[derive(Debug, Default, Clone, Copy)]
+struct Options {
+    lenient: bool,
+    continue_on_error: bool,
+    numeral_mode: NumeralMode,
+}
+
+impl Options {
+    /// Take the option flags out of `args`, starting from the `.numerusrc`
+    /// defaults; a flag given on the command line always wins, and each
+    /// setting has a flag for either value so the rc file can be overridden
+    fn from_args(args: &mut Vec<String>, config: &Config) -> Self {
+        let (lenient, strict) = (take_flag(args, "--lenient"), take_flag(args, "--no-lenient"));
+        let (arabic, roman) = (take_flag(args, "--arabic-only"), take_flag(args, "--roman-numerals"));
+        Options {
+            lenient: match (lenient, strict) {
+                (true, _) => true,
+                (_, true) => false,
+                _ => config.lenient,
+            },
+            continue_on_error: take_flag(args, "--continue-on-error"),
+            numeral_mode: match (arabic, roman) {
+                (true, _) => NumeralMode::ArabicOnly,
+                (_, true) => NumeralMode::Roman,
+                _ => config.numeral_mode,
+            },
+        }
+    }
+}
+
+/// Convert a single numeral for `--roman` (Roman to Arabic) or `--arabic`
+/// (Arabic to Roman), returning the converted text or the error message
+fn convert_numeral(mode: &str, input: &str, lenient: bool) -> Result<String, String> {
+    if mode == "--roman" {
+        let parsed = if lenient { from_roman_lenient(input) } else { from_roman(input) };
+        parsed.map(|n| n.to_string()).map_err(|e| e.to_string())
+    } else {
+        let n: i32 = input
+            .parse()
+            .map_err(|_| format!("ERRATUM: '{}' non est numerus Arabicus!", input))?;
+        to_roman(n).map_err(|e| e.to_string())
+    }
+}
+
+/// Wall-clock time spent in each phase of running a program
+#[derive(Debug, Default, Clone, Copy)]
+struct Timings {
+    lexing: Duration,
+    parsing: Duration,
+    execution: Duration,
+}
+
+impl Timings {
+    fn total(&self) -> Duration {
+        self.lexing + self.parsing + self.execution
+    }
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TEMPUS: lexis {:?}, syntaxis {:?}, exsecutio {:?} (summa {:?})",
+            self.lexing,
+            self.parsing,
+            self.execution,
+            self.total()
+        )
+    }
+}
+
+/// Run a complete Numerus++ program, timing each phase
+///
+/// Each runtime error is passed to `on_error`, with the span of the statement
+/// that raised it, as it happens, and all are returned alongside the timings.
+/// The first one stops the program unless `continue_on_error` is set. Lexing
+/// and parsing errors fail instead.
+fn run_program(
+    source: &str,
+    options: &Options,
+    mut on_error: impl FnMut(&NumerusError, Span),
+) -> Result<(Timings, Vec<NumerusError>), NumerusError> {
+    let mut timings = Timings::default();
+
+    let start = Instant::now();
     let mut lexer = Lexer::new(source);
+    lexer.set_lenient_numerals(options.lenient);
+    lexer.set_numeral_mode(options.numeral_mode);
     let tokens = lexer.tokenize()?;
+    timings.lexing = start.elapsed();
 
+    let start = Instant::now();
     let mut parser = Parser::new(tokens);
     let program = parser.parse()?;
+    timings.parsing = start.elapsed();
 
+    let start = Instant::now();
     let mut interpreter = Interpreter::new();
-    interpreter.run(&program)?;
+    interpreter.set_on_print(Box::new(|line| println!("{}", line)));
+    interpreter.set_on_error_print(Box::new(|line| eprintln!("{}", line)));
+    interpreter.set_numeral_mode(options.numeral_mode);
+    let errors = if options.continue_on_error {
+        interpreter.run_lenient_with(&program, on_error).1
+    } else {
+        match interpreter.run(&program) {
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                let statement = interpreter.failed_statement().unwrap_or_default();
+                on_error(&e, statement);
+                vec![e]
+            }
+        }
+    };
+    timings.execution = start.elapsed();
 
-    Ok(())
+    Ok((timings, errors))
 }
 
-/// Check program syntax and output diagnostics as JSON
-fn check_program(source: &str) {
+/// Check program syntax and build the JSON report: whether the whole program
+/// parsed (`ok`), its diagnostics, and on success the number of statements
+fn check_program(source: &str, options: &Options) -> String {
     let mut diagnostics = Vec::new();
 
     // Try lexing
     let mut lexer = Lexer::new(source);
+    lexer.set_lenient_numerals(options.lenient);
+    lexer.set_numeral_mode(options.numeral_mode);
     let tokens = match lexer.tokenize() {
         Ok(t) => t,
         Err(e) => {
             diagnostics.push(error_to_diagnostic(&e, source));
-            print_diagnostics(&diagnostics);
-            return;
+            return check_report(&diagnostics, None);
         }
     };
 
+    for (found, canonical, span) in find_non_canonical_numerals(&tokens) {
+        let message = format!(
+            "MONITUM: Numerus Romanus '{}' non est forma canonica; scribe '{}'.",
+            found, canonical
+        );
+        diagnostics.push(span_to_diagnostic(&span, "warning", &message));
+    }
+
+    // Without Roman literals every such word is an ordinary name
+    if options.numeral_mode == NumeralMode::Roman {
+        for (name, reason, span) in find_invalid_numerals(&tokens, options.lenient) {
+            let message = format!("MONITUM: '{}' non est numerus Romanus validus, ergo nomen est. {}", name, reason);
+            diagnostics.push(span_to_diagnostic(&span, "warning", &message));
+        }
+    }
+
     // Try parsing
     let mut parser = Parser::new(tokens);
-    if let Err(e) = parser.parse() {
-        diagnostics.push(error_to_diagnostic(&e, source));
+    match parser.parse() {
+        Ok(program) => {
+            for (name, span) in find_dead_stores(&program) {
+                let message = format!(
+                    "MONITUM: Valor variabilis '{}' numquam legitur antequam iterum assignatur!",
+                    name
+                );
+                diagnostics.push(span_to_diagnostic(&span, "warning", &message));
+            }
+            check_report(&diagnostics, Some(program.statements.len()))
+        }
+        Err(e) => {
+            diagnostics.push(error_to_diagnostic(&e, source));
+            check_report(&diagnostics, None)
+        }
     }
-
-    print_diagnostics(&diagnostics);
 }
 
 /// Convert a NumerusError to a diagnostic JSON object
@@ -146,6 +382,14 @@ fn error_to_diagnostic(error: &NumerusError, source: &str) -> String {
                 NumerusError::UnexpectedCharacter { line, column, .. } => {
                     (*line, *column, *line, *column + 1)
                 }
+                NumerusError::IdentifierTooLong { max, line, column } => {
+                    (*line, *column, *line, *column + max)
+                }
+                NumerusError::InvalidEscape { sequence, line, column }
+                | NumerusError::UnsupportedNumberFormat { found: sequence, line, column }
+                | NumerusError::FractionalNotSupported { found: sequence, line, column } => {
+                    (*line, *column, *line, *column + sequence.chars().count())
+                }
                 NumerusError::UnterminatedString { line } => {
                     (*line, 1, *line, source.lines().nth(line.saturating_sub(1)).map(|l| l.len()).unwrap_or(1))
                 }
@@ -154,17 +398,68 @@ fn error_to_diagnostic(error: &NumerusError, source: &str) -> String {
         }
     };
 
-    let message = error.to_string().replace('"', "\\\"").replace('\n', " ");
+    diagnostic(line, column, end_line, end_column, "error", &error.to_string())
+}
 
+/// Convert a span with a message to a diagnostic JSON object
+fn span_to_diagnostic(span: &Span, severity: &str, message: &str) -> String {
+    let end_column = span.column + (span.end - span.start).max(1);
+    diagnostic(span.line, span.column, span.line, end_column, severity, message)
+}
+
+/// Format a single diagnostic JSON object
+fn diagnostic(
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    severity: &str,
+    message: &str,
+) -> String {
     format!(
-        r#"{{"line":{},"column":{},"end_line":{},"end_column":{},"severity":"error","message":"{}"}}"#,
-        line, column, end_line, end_column, message
+        r#"{{"line":{},"column":{},"end_line":{},"end_column":{},"severity":"{}","message":"{}"}}"#,
+        line,
+        column,
+        end_line,
+        end_column,
+        severity,
+        json_escape(message)
     )
 }
 
-/// Print diagnostics as JSON
-fn print_diagnostics(diagnostics: &[String]) {
-    println!(r#"{{"diagnostics":[{}]}}"#, diagnostics.join(","));
+/// Escape text for use inside a JSON string literal
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// The --check JSON report for a file that could not be read
+fn read_error_report(error: &dyn std::fmt::Display) -> String {
+    let message = format!("Cannot read file: {}", error);
+    check_report(&[diagnostic(1, 1, 1, 1, "error", &message)], None)
+}
+
+/// Format the --check JSON report; `statements` is `None` when parsing failed
+fn check_report(diagnostics: &[String], statements: Option<usize>) -> String {
+    match statements {
+        Some(count) => format!(
+            r#"{{"ok":true,"diagnostics":[{}],"statements":{}}}"#,
+            diagnostics.join(","),
+            count
+        ),
+        None => format!(r#"{{"ok":false,"diagnostics":[{}]}}"#, diagnostics.join(",")),
+    }
 }
 
 fn print_usage() {
@@ -173,9 +468,26 @@ fn print_usage() {
     println!("Usus:");
     println!("  numerus              - Incipe REPL (modus interactivus)");
     println!("  numerus <file.npp>   - Exsequi file Numerus++");
+    println!("  numerus --check <f>  - Inspice syntaxim (JSON)");
+    println!("  numerus --fix-numerals <f>");
+    println!("                       - Corrige numeros Romanos ad formam canonicam");
+    println!("  numerus --grammar    - Monstra grammaticam (JSON)");
+    println!("  numerus --roman <R>  - Converte numerum Romanum ad Arabicum");
+    println!("  numerus --arabic <N> - Converte numerum Arabicum ad Romanum");
     println!("  numerus --help       - Monstra hoc auxilium");
     println!("  numerus --version    - Monstra versionem");
     println!();
+    println!("Optiones:");
+    println!("  --lenient            - Accipe numeros Romanos additivos (IIII)");
+    println!("  --no-lenient         - Solum formae canonicae (praefinitum)");
+    println!("  --time               - Monstra tempus lexis, syntaxis et exsecutionis");
+    println!("  --continue-on-error  - Post errata perge ad sententiam proximam");
+    println!("  --arabic-only        - Solum numeri Arabici, sine limite MMMCMXCIX");
+    println!("  --roman-numerals     - Numeri Romani (praefinitum)");
+    println!();
+    println!("Praeferentiae in .numerusrc (directorium currens vel domus):");
+    println!("  numerals = roman|arabic, lenient = on|off, color = on|off, prompt = \"> \"");
+    println!();
     println!("Exemplum:");
     println!("  {} example.npp", "numerus".green());
     println!();
@@ -185,3 +497,128 @@ fn print_version() {
     println!("{} {}", "NUMERUS++".bright_yellow().bold(), env!("CARGO_PKG_VERSION"));
     println!("Roma Aeterna Est!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_program_timings() {
+        let (timings, errors) = run_program("DECLARA X EST XV\nX EST X MULTIPLICA XX", &Options::default(), |_, _| {}).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(timings.total(), timings.lexing + timings.parsing + timings.execution);
+        assert!(timings.to_string().starts_with("TEMPUS: lexis "));
+    }
+
+    #[test]
+    fn test_check_program_valid() {
+        let report = check_program("DECLARA X EST XV\nSCRIBE(X)\nAVTEM", &Options::default());
+        assert_eq!(report, r#"{"ok":true,"diagnostics":[],"statements":3}"#);
+    }
+
+    #[test]
+    fn test_check_program_warnings_are_ok() {
+        let report = check_program("DECLARA X EST XV\nX EST XX", &Options::default());
+        assert!(report.starts_with(r#"{"ok":true,"diagnostics":[{"#), "{}", report);
+        assert!(report.ends_with(r#""statements":2}"#), "{}", report);
+    }
+
+    #[test]
+    fn test_check_program_invalid() {
+        let report = check_program("DECLARA X EST", &Options::default());
+        assert!(report.starts_with(r#"{"ok":false,"diagnostics":[{"#), "{}", report);
+        assert!(!report.contains("statements"), "{}", report);
+
+        let report = check_program("SCRIBE(@)", &Options::default());
+        assert!(report.starts_with(r#"{"ok":false,"#), "{}", report);
+    }
+
+    #[test]
+    fn test_check_program_explains_invalid_numeral() {
+        let report = check_program("DECLARA X EST VX", &Options::default());
+        assert!(report.starts_with(r#"{"ok":true,"diagnostics":[{"#), "{}", report);
+        assert!(report.contains("'VX' subtractivum invalidum"), "{}", report);
+
+        let arabic = Options { numeral_mode: NumeralMode::ArabicOnly, ..Options::default() };
+        assert_eq!(check_program("DECLARA X EST VX", &arabic), r#"{"ok":true,"diagnostics":[],"statements":1}"#);
+    }
+
+    #[test]
+    fn test_read_error_report_is_valid_json() {
+        let error = "C:\\numerus\\\"missing\".npp\nnot found\u{7}";
+        let report: serde_json::Value = serde_json::from_str(&read_error_report(&error)).unwrap();
+        assert_eq!(report["ok"], false);
+        assert_eq!(report["diagnostics"][0]["message"], format!("Cannot read file: {}", error));
+
+        let report = check_program("SCRIBE(\"a\\\\b\" ADDIUS)", &Options::default());
+        assert!(serde_json::from_str::<serde_json::Value>(&report).is_ok(), "{}", report);
+    }
+
+    #[test]
+    fn test_run_program_error_has_no_timings() {
+        let continuing = Options { continue_on_error: true, ..Options::default() };
+        assert!(run_program("DECLARA X EST", &Options::default(), |_, _| {}).is_err());
+        assert!(run_program("DECLARA X EST", &continuing, |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn test_convert_numeral() {
+        assert_eq!(convert_numeral("--roman", "XLII", false), Ok("42".to_string()));
+        assert_eq!(convert_numeral("--arabic", "42", false), Ok("XLII".to_string()));
+        assert_eq!(convert_numeral("--roman", "IIII", true), Ok("4".to_string()));
+
+        let invalid = convert_numeral("--roman", "IIII", false).unwrap_err();
+        assert_eq!(invalid, from_roman("IIII").unwrap_err().to_string());
+        let out_of_range = convert_numeral("--arabic", "4000", false).unwrap_err();
+        assert!(out_of_range.contains("1–3999 / I–MMMCMXCIX"), "{}", out_of_range);
+        assert!(convert_numeral("--arabic", "XLII", false).unwrap_err().contains("non est numerus Arabicus"));
+    }
+
+    #[test]
+    fn test_run_program_continue_on_error() {
+        let source = "SCRIBE(\"I\")\nSCRIBE(XX DIVIDE 0)\nSCRIBE(\"III\")\nSCRIBE(X)";
+        let mut reported = Vec::new();
+        let (_, errors) = run_program(source, &Options::default(), |_, statement| reported.push(statement.line)).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(reported, vec![2]);
+
+        let continuing = Options { continue_on_error: true, ..Options::default() };
+        let mut reported = Vec::new();
+        let (_, errors) = run_program(source, &continuing, |_, statement| reported.push(statement.line)).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(reported, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_arabic_only_option() {
+        let arabic_only = Options { numeral_mode: NumeralMode::ArabicOnly, ..Options::default() };
+        assert!(run_program("DECLARA XIV EST 4000", &Options::default(), |_, _| {}).is_err());
+        assert!(run_program("DECLARA XIV EST 4000", &arabic_only, |_, _| {}).is_ok());
+        assert!(check_program("DECLARA XIV EST 4000", &arabic_only).starts_with(r#"{"ok":true"#));
+    }
+
+    #[test]
+    fn test_flags_override_config() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let config = Config::parse("numerals = arabic\nlenient = on").unwrap();
+        let mut plain = args(&["numerus", "file.npp"]);
+        let options = Options::from_args(&mut plain, &config);
+        assert_eq!(options.numeral_mode, NumeralMode::ArabicOnly);
+        assert!(options.lenient);
+        assert_eq!(plain, args(&["numerus", "file.npp"]));
+
+        let mut flagged = args(&["numerus", "--arabic-only", "--lenient", "file.npp"]);
+        let options = Options::from_args(&mut flagged, &Config::default());
+        assert_eq!(options.numeral_mode, NumeralMode::ArabicOnly);
+        assert!(options.lenient);
+        assert_eq!(flagged, args(&["numerus", "file.npp"]));
+
+        // Every rc setting can be switched back for a single run
+        let mut reverted = args(&["numerus", "--roman-numerals", "--no-lenient", "file.npp"]);
+        let options = Options::from_args(&mut reverted, &config);
+        assert_eq!(options.numeral_mode, NumeralMode::Roman);
+        assert!(!options.lenient);
+        assert_eq!(reverted, args(&["numerus", "file.npp"]));
+    }
+}
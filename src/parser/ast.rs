@@ -1,4 +1,7 @@
+use std::fmt;
+
 use crate::lexer::Span;
+use crate::roman::to_roman;
 
 /// The root of the AST - a program is a list of statements
 #[derive(Debug, Clone, PartialEq)]
@@ -10,14 +13,30 @@ impl Program {
     pub fn new(statements: Vec<Statement>) -> Self {
         Self { statements }
     }
+
+    /// Each declared variable name with the span of its declaration (for tooling)
+    pub fn declarations(&self) -> impl Iterator<Item = (&str, Span)> {
+        self.statements.iter().filter_map(|statement| match statement {
+            Statement::Declaration { name, span, .. } => Some((name.as_str(), *span)),
+            _ => None,
+        })
+    }
+
+    /// Compare two programs ignoring spans, so the same code formatted
+    /// differently compares equal
+    pub fn structurally_eq(&self, other: &Program) -> bool {
+        self.statements.len() == other.statements.len()
+            && self.statements.iter().zip(&other.statements).all(|(a, b)| a.structurally_eq(b))
+    }
 }
 
 /// All statement types in Numerus++
 #[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
-    /// DECLARA <IDENT> EST <VALUE>
+    /// DECLARA <IDENT> [: <TYPE>] EST <VALUE>
     Declaration {
         name: String,
+        declared_type: Option<TypeAnnotation>,
         value: Expression,
         span: Span,
     },
@@ -29,25 +48,86 @@ pub enum Statement {
         span: Span,
     },
 
-    /// SCRIBE(expression)
-    /// Expression can be a string, number, or concatenation
+    /// SCRIBE(expression) or SCRIBE("format {}", arg, ...)
+    /// Expression can be a string, number, or concatenation; any extra
+    /// arguments are substituted into the `{}` / `{N}` placeholders of the first
     Print {
         value: Expression,
+        arguments: Vec<Expression>,
+        span: Span,
+    },
+
+    /// SCRIBE_ERRATUM(expression) - like SCRIBE, but to the error stream
+    PrintError {
+        value: Expression,
+        span: Span,
+    },
+
+    /// MONSTRA_TABULAM(start, finis) - print Arabic/Roman pairs from start to finis
+    Table {
+        start: Expression,
+        end: Expression,
         span: Span,
     },
 
-    /// AVTEM - ceremonial no-op
+    /// AVTEM or AVTEM("note") - ceremonial no-op, optionally leaving a note
     Avtem {
+        message: Option<String>,
         span: Span,
     },
 
-    /// NOTA: ... - comment (preserved in AST for tooling)
+    /// NOTA: ... - comment (preserved in AST for tooling); `body_span`
+    /// covers just the text, without the `NOTA:` marker
     Comment {
         text: String,
         span: Span,
+        body_span: Span,
     },
 }
 
+impl Statement {
+    /// Get the span of this statement
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::Declaration { span, .. } => *span,
+            Statement::Assignment { span, .. } => *span,
+            Statement::Print { span, .. } => *span,
+            Statement::PrintError { span, .. } => *span,
+            Statement::Table { span, .. } => *span,
+            Statement::Avtem { span, .. } => *span,
+            Statement::Comment { span, .. } => *span,
+        }
+    }
+
+    /// Compare two statements ignoring spans
+    pub fn structurally_eq(&self, other: &Statement) -> bool {
+        match (self, other) {
+            (
+                Statement::Declaration { name: n1, declared_type: t1, value: v1, .. },
+                Statement::Declaration { name: n2, declared_type: t2, value: v2, .. },
+            ) => n1 == n2 && t1 == t2 && v1.structurally_eq(v2),
+            (
+                Statement::Assignment { name: n1, value: v1, .. },
+                Statement::Assignment { name: n2, value: v2, .. },
+            ) => n1 == n2 && v1.structurally_eq(v2),
+            (
+                Statement::Print { value: v1, arguments: a1, .. },
+                Statement::Print { value: v2, arguments: a2, .. },
+            ) => v1.structurally_eq(v2) && all_structurally_eq(a1, a2),
+            (Statement::PrintError { value: v1, .. }, Statement::PrintError { value: v2, .. }) => {
+                v1.structurally_eq(v2)
+            }
+            (
+                Statement::Table { start: s1, end: e1, .. },
+                Statement::Table { start: s2, end: e2, .. },
+            ) => s1.structurally_eq(s2) && e1.structurally_eq(e2),
+            (Statement::Avtem { message: m1, .. }, Statement::Avtem { message: m2, .. }) => m1 == m2,
+            (Statement::Comment { text: t1, .. }, Statement::Comment { text: t2, .. }) => t1 == t2,
+            _ => false,
+        }
+    }
+}
+
 /// Expression AST node
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
@@ -64,6 +144,18 @@ pub enum Expression {
         span: Span,
     },
 
+    /// A boolean literal: VERUM or FALSUM
+    BooleanLiteral {
+        value: bool,
+        span: Span,
+    },
+
+    /// A list literal: {a, b, c}
+    ListLiteral {
+        elements: Vec<Expression>,
+        span: Span,
+    },
+
     /// Variable reference
     Variable {
         name: String,
@@ -84,10 +176,10 @@ pub enum Expression {
         span: Span,
     },
 
-    /// Built-in function call: ROMANIZA(n) or EXPRIME(s)
+    /// Built-in function call: ROMANIZA(n) or COMPARA(a, b)
     FunctionCall {
         function: BuiltinFunction,
-        argument: Box<Expression>,
+        arguments: Vec<Expression>,
         span: Span,
     },
 }
@@ -98,12 +190,132 @@ impl Expression {
         match self {
             Expression::NumberLiteral { span, .. } => *span,
             Expression::StringLiteral { span, .. } => *span,
+            Expression::BooleanLiteral { span, .. } => *span,
+            Expression::ListLiteral { span, .. } => *span,
             Expression::Variable { span, .. } => *span,
             Expression::BinaryOp { span, .. } => *span,
             Expression::Grouped { span, .. } => *span,
             Expression::FunctionCall { span, .. } => *span,
         }
     }
+
+    /// Compare two expressions ignoring spans
+    pub fn structurally_eq(&self, other: &Expression) -> bool {
+        match (self, other) {
+            (
+                Expression::NumberLiteral { value: v1, original_form: f1, .. },
+                Expression::NumberLiteral { value: v2, original_form: f2, .. },
+            ) => v1 == v2 && f1 == f2,
+            (Expression::StringLiteral { value: v1, .. }, Expression::StringLiteral { value: v2, .. }) => v1 == v2,
+            (Expression::BooleanLiteral { value: v1, .. }, Expression::BooleanLiteral { value: v2, .. }) => v1 == v2,
+            (Expression::ListLiteral { elements: e1, .. }, Expression::ListLiteral { elements: e2, .. }) => {
+                all_structurally_eq(e1, e2)
+            }
+            (Expression::Variable { name: n1, .. }, Expression::Variable { name: n2, .. }) => n1 == n2,
+            (
+                Expression::BinaryOp { left: l1, operator: o1, right: r1, .. },
+                Expression::BinaryOp { left: l2, operator: o2, right: r2, .. },
+            ) => o1 == o2 && l1.structurally_eq(l2) && r1.structurally_eq(r2),
+            (Expression::Grouped { inner: i1, .. }, Expression::Grouped { inner: i2, .. }) => {
+                i1.structurally_eq(i2)
+            }
+            (
+                Expression::FunctionCall { function: f1, arguments: a1, .. },
+                Expression::FunctionCall { function: f2, arguments: a2, .. },
+            ) => f1 == f2 && all_structurally_eq(a1, a2),
+            _ => false,
+        }
+    }
+}
+
+/// Source code for a statement, on one line (used to show which statement failed)
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statement::Declaration { name, declared_type, value, .. } => {
+                write!(f, "DECLARA {}", name)?;
+                if let Some(annotation) = declared_type {
+                    write!(f, ": {}", annotation.name())?;
+                }
+                write!(f, " EST {}", value)
+            }
+            Statement::Assignment { name, value, .. } => write!(f, "{} EST {}", name, value),
+            Statement::Print { value, arguments, .. } => {
+                write!(f, "SCRIBE({}", value)?;
+                for argument in arguments {
+                    write!(f, ", {}", argument)?;
+                }
+                write!(f, ")")
+            }
+            Statement::PrintError { value, .. } => write!(f, "SCRIBE_ERRATUM({})", value),
+            Statement::Table { start, end, .. } => write!(f, "MONSTRA_TABULAM({}, {})", start, end),
+            Statement::Avtem { message: None, .. } => write!(f, "AVTEM"),
+            Statement::Avtem { message: Some(message), .. } => write!(f, "AVTEM({})", quoted(message)),
+            Statement::Comment { text, .. } => write!(f, "NOTA: {}", text),
+        }
+    }
+}
+
+/// Source code for an expression, with literals in the form they were written
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::NumberLiteral { value, original_form: NumberForm::Roman, .. } => match to_roman(*value) {
+                Ok(roman) => write!(f, "{}", roman),
+                Err(_) => write!(f, "{}", value),
+            },
+            Expression::NumberLiteral { value, .. } => write!(f, "{}", value),
+            Expression::StringLiteral { value, .. } => write!(f, "{}", quoted(value)),
+            Expression::BooleanLiteral { value, .. } => write!(f, "{}", if *value { "VERUM" } else { "FALSUM" }),
+            Expression::ListLiteral { elements, .. } => {
+                write!(f, "{{")?;
+                write_separated(f, elements)?;
+                write!(f, "}}")
+            }
+            Expression::Variable { name, .. } => write!(f, "{}", name),
+            Expression::BinaryOp { left, operator, right, .. } => {
+                write!(f, "{} {} {}", left, operator.symbol(), right)
+            }
+            Expression::Grouped { inner, .. } => write!(f, "({})", inner),
+            Expression::FunctionCall { function, arguments, .. } => {
+                write!(f, "{}(", function.name())?;
+                write_separated(f, arguments)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn write_separated(f: &mut fmt::Formatter<'_>, expressions: &[Expression]) -> fmt::Result {
+    for (i, expression) in expressions.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", expression)?;
+    }
+    Ok(())
+}
+
+/// A string literal as it would be written in source, escapes included
+fn quoted(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(ch),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Pairwise [`Expression::structurally_eq`] over two lists of expressions
+fn all_structurally_eq(a: &[Expression], b: &[Expression]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.structurally_eq(y))
 }
 
 /// Tracks whether a literal was written as Roman or Arabic
@@ -113,6 +325,22 @@ pub enum NumberForm {
     Roman,
 }
 
+/// Optional type annotation on a declaration: `DECLARA X: NUMERUS EST V`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAnnotation {
+    Numerus,    // NUMERUS - numbers
+    Chorda,     // CHORDA - strings
+}
+
+impl TypeAnnotation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypeAnnotation::Numerus => "NUMERUS",
+            TypeAnnotation::Chorda => "CHORDA",
+        }
+    }
+}
+
 /// Binary operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinaryOperator {
@@ -123,6 +351,22 @@ pub enum BinaryOperator {
 }
 
 impl BinaryOperator {
+    /// Every operator, in declaration order
+    pub const ALL: [BinaryOperator; 4] = [
+        BinaryOperator::Add,
+        BinaryOperator::Subtract,
+        BinaryOperator::Multiply,
+        BinaryOperator::Divide,
+    ];
+
+    /// Binding strength: higher binds tighter
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Add | BinaryOperator::Subtract => 1,
+            BinaryOperator::Multiply | BinaryOperator::Divide => 2,
+        }
+    }
+
     pub fn symbol(&self) -> &'static str {
         match self {
             BinaryOperator::Add => "ADDIUS",
@@ -133,11 +377,176 @@ impl BinaryOperator {
     }
 }
 
+/// Each built-in function's name and description, in declaration order
+static BUILTIN_FUNCTIONS: [(&str, &str); BuiltinFunction::ALL.len()] = {
+    let mut entries = [("", ""); BuiltinFunction::ALL.len()];
+    let mut i = 0;
+    while i < entries.len() {
+        entries[i] = (BuiltinFunction::ALL[i].name(), BuiltinFunction::ALL[i].description());
+        i += 1;
+    }
+    entries
+};
+
+/// Every built-in function as `(name, description)`, e.g. for AUXILIUM or editor tooling
+pub fn builtin_functions() -> &'static [(&'static str, &'static str)] {
+    &BUILTIN_FUNCTIONS
+}
+
 /// Built-in functions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuiltinFunction {
     Romaniza,   // Convert number to Roman numeral string
     Arabiza,    // Convert to Arabic number (for display as decimal)
-    Exprime,    // Convert Roman string to Arabic (for future string support)
+    Exprime,    // Read a number from a Roman or Arabic string
+    Compara,    // Compare two values: -1, 0 or 1
+    Primus,     // Whether a number is prime
+    Factores,   // Prime factors of a number, as a list
+    Progressio, // Arithmetic sequence from start to finis by gradus, as a list
+    Uncia,      // Roman name of a fraction in twelfths (1..=12)
+    Sorta,      // Sort a list, ascending unless the second argument is VERUM
+    Adde,       // New list with a value appended
+    Remove,     // New list without the element at a 1-based index
+    ExAmbitu,   // Value of an OS environment variable, as a string
+    Hex,        // Number written as a hexadecimal string
+    Verte,      // Reverse a string (by character) or a list
+    DivideCumResiduo, // Quotient and remainder of a division, as a list
+    Duplica,    // Twice a number
+    Dimidium,   // Half a number, rounded toward zero
+    RomanizaMinuscula, // Convert number to a lowercase Roman numeral string
+    SummaUsque, // Sum 1 + 2 + ... + n
+    SummaInter, // Sum a + (a + 1) + ... + b
+    Inspice,    // Print a value for debugging and return it unchanged
+    Typus,      // Name of a value's type
+    Par,        // Whether a number is even
+    Impar,      // Whether a number is odd
+}
+
+impl BuiltinFunction {
+    /// Every built-in function, in declaration order
+    pub const ALL: [BuiltinFunction; 24] = [
+        BuiltinFunction::Romaniza,
+        BuiltinFunction::Arabiza,
+        BuiltinFunction::Exprime,
+        BuiltinFunction::Compara,
+        BuiltinFunction::Primus,
+        BuiltinFunction::Factores,
+        BuiltinFunction::Progressio,
+        BuiltinFunction::Uncia,
+        BuiltinFunction::Sorta,
+        BuiltinFunction::Adde,
+        BuiltinFunction::Remove,
+        BuiltinFunction::ExAmbitu,
+        BuiltinFunction::Hex,
+        BuiltinFunction::Verte,
+        BuiltinFunction::DivideCumResiduo,
+        BuiltinFunction::Duplica,
+        BuiltinFunction::Dimidium,
+        BuiltinFunction::RomanizaMinuscula,
+        BuiltinFunction::SummaUsque,
+        BuiltinFunction::SummaInter,
+        BuiltinFunction::Inspice,
+        BuiltinFunction::Typus,
+        BuiltinFunction::Par,
+        BuiltinFunction::Impar,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            BuiltinFunction::Romaniza => "ROMANIZA",
+            BuiltinFunction::Arabiza => "ARABIZA",
+            BuiltinFunction::Exprime => "EXPRIME",
+            BuiltinFunction::Compara => "COMPARA",
+            BuiltinFunction::Primus => "PRIMUS",
+            BuiltinFunction::Factores => "FACTORES",
+            BuiltinFunction::Progressio => "PROGRESSIO",
+            BuiltinFunction::Uncia => "UNCIA",
+            BuiltinFunction::Sorta => "SORTA",
+            BuiltinFunction::Adde => "ADDE",
+            BuiltinFunction::Remove => "REMOVE",
+            BuiltinFunction::ExAmbitu => "EX_AMBITU",
+            BuiltinFunction::Hex => "HEX",
+            BuiltinFunction::Verte => "VERTE",
+            BuiltinFunction::DivideCumResiduo => "DIVIDE_CUM_RESIDUO",
+            BuiltinFunction::Duplica => "DUPLICA",
+            BuiltinFunction::Dimidium => "DIMIDIUM",
+            BuiltinFunction::RomanizaMinuscula => "ROMANIZA_MINUSCULA",
+            BuiltinFunction::SummaUsque => "SUMMA_USQUE",
+            BuiltinFunction::SummaInter => "SUMMA_INTER",
+            BuiltinFunction::Inspice => "INSPICE",
+            BuiltinFunction::Typus => "TYPUS",
+            BuiltinFunction::Par => "PAR",
+            BuiltinFunction::Impar => "IMPAR",
+        }
+    }
+
+    /// One-line Latin description, for help text and tooling
+    pub const fn description(&self) -> &'static str {
+        match self {
+            BuiltinFunction::Romaniza => "Converte numerum ad chordam Romanam",
+            BuiltinFunction::Arabiza => "Converte numerum ad chordam Arabicam",
+            BuiltinFunction::Exprime => "Lege numerum ex chorda Romana vel Arabica",
+            BuiltinFunction::Compara => "Compara duos valores: -1, 0 vel 1",
+            BuiltinFunction::Primus => "VERUM si numerus primus est",
+            BuiltinFunction::Factores => "Factores primi numeri, ut copia",
+            BuiltinFunction::Progressio => "Copia a principio ad finem per gradum",
+            BuiltinFunction::Uncia => "Nomen fractionis in unciis (I-XII)",
+            BuiltinFunction::Sorta => "Ordina copiam (VERUM: descendens)",
+            BuiltinFunction::Adde => "Nova copia cum valore addito",
+            BuiltinFunction::Remove => "Nova copia sine elemento ad indicem",
+            BuiltinFunction::ExAmbitu => "Variabilis ambitus systematis, ut chorda",
+            BuiltinFunction::Hex => "Lege numerum hexadecimalem ex chorda",
+            BuiltinFunction::Verte => "Inverte chordam vel copiam",
+            BuiltinFunction::DivideCumResiduo => "Quotus et residuum divisionis, ut copia",
+            BuiltinFunction::Duplica => "Duplum numeri",
+            BuiltinFunction::Dimidium => "Dimidium numeri (sine residuo)",
+            BuiltinFunction::RomanizaMinuscula => "Converte numerum ad Romanos minusculos",
+            BuiltinFunction::SummaUsque => "Summa I + II + ... + n",
+            BuiltinFunction::SummaInter => "Summa a + (a + I) + ... + b",
+            BuiltinFunction::Inspice => "Imprime valorem et reddit eum",
+            BuiltinFunction::Typus => "Nomen typi valoris",
+            BuiltinFunction::Par => "VERUM si numerus par est",
+            BuiltinFunction::Impar => "VERUM si numerus impar est",
+        }
+    }
+
+    /// Number of arguments the function takes
+    pub fn arity(&self) -> usize {
+        match self {
+            BuiltinFunction::Romaniza => 1,
+            BuiltinFunction::Arabiza => 1,
+            BuiltinFunction::Exprime => 1,
+            BuiltinFunction::Compara => 2,
+            BuiltinFunction::Primus => 1,
+            BuiltinFunction::Factores => 1,
+            BuiltinFunction::Progressio => 3,
+            BuiltinFunction::Uncia => 1,
+            BuiltinFunction::Sorta => 1,
+            BuiltinFunction::Adde => 2,
+            BuiltinFunction::Remove => 2,
+            BuiltinFunction::ExAmbitu => 1,
+            BuiltinFunction::Hex => 1,
+            BuiltinFunction::Verte => 1,
+            BuiltinFunction::DivideCumResiduo => 2,
+            BuiltinFunction::Duplica => 1,
+            BuiltinFunction::Dimidium => 1,
+            BuiltinFunction::RomanizaMinuscula => 1,
+            BuiltinFunction::SummaUsque => 1,
+            BuiltinFunction::SummaInter => 2,
+            BuiltinFunction::Inspice => 1,
+            BuiltinFunction::Typus => 1,
+            BuiltinFunction::Par => 1,
+            BuiltinFunction::Impar => 1,
+        }
+    }
+
+    /// Largest number of arguments the function accepts (optional arguments
+    /// come after the `arity` required ones)
+    pub fn max_arity(&self) -> usize {
+        match self {
+            BuiltinFunction::Sorta => 2,
+            _ => self.arity(),
+        }
+    }
 }
 
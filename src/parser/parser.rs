@@ -12,6 +12,22 @@ impl Parser {
         Self { tokens, current: 0 }
     }
 
+    /// Rewind to the first token so the same tokens can be parsed again
+    pub fn reset(&mut self) {
+        self.current = 0;
+    }
+
+    /// Index of the next token to be consumed
+    pub fn position(&self) -> usize {
+        self.current
+    }
+
+    /// Move to the token at `position` (for backtracking), clamped to the
+    /// final end-of-input token
+    pub fn set_position(&mut self, position: usize) {
+        self.current = position.min(self.tokens.len().saturating_sub(1));
+    }
+
     /// Parse the entire program
     pub fn parse(&mut self) -> Result<Program, NumerusError> {
         let mut statements = Vec::new();
@@ -23,11 +39,32 @@ impl Parser {
         Ok(Program::new(statements))
     }
 
+    /// Parse the tokens as a single expression (rather than a program),
+    /// failing if anything is left over after it; the lexer already drops
+    /// blank lines, and kept comments before or after the expression are skipped
+    pub fn parse_expression_entry(&mut self) -> Result<Expression, NumerusError> {
+        self.skip_continuation_comments();
+        let expression = self.parse_expression()?;
+        self.skip_continuation_comments();
+
+        if !self.is_at_end() {
+            return Err(NumerusError::UnexpectedToken {
+                expected: "finis".to_string(),
+                found: self.peek().describe(),
+                span: self.peek().span,
+            });
+        }
+
+        Ok(expression)
+    }
+
     /// Parse a single statement
     fn parse_statement(&mut self) -> Result<Statement, NumerusError> {
         match &self.peek().kind {
             TokenKind::Declara => self.parse_declaration(),
             TokenKind::Scribe => self.parse_print(),
+            TokenKind::ScribeErratum => self.parse_print_error(),
+            TokenKind::MonstraTabulam => self.parse_table(),
             TokenKind::Avtem => self.parse_avtem(),
             TokenKind::Comment(text) => {
                 let text = text.clone();
@@ -35,33 +72,56 @@ impl Parser {
                 Ok(Statement::Comment {
                     text,
                     span: token.span,
+                    body_span: token.comment_body_span().unwrap_or(token.span),
                 })
             }
             TokenKind::Identifier(_) => self.parse_assignment(),
-            TokenKind::Eof => Err(NumerusError::UnexpectedEndOfInput),
-            _ => Err(NumerusError::UnexpectedToken {
-                expected: "DECLARA, SCRIBE, AVTEM, or identifier".to_string(),
-                found: format!("{}", self.peek().kind),
+            TokenKind::Est => Err(NumerusError::MissingVariableName { span: self.peek().span }),
+            kind if kind.is_operator() => Err(NumerusError::MissingLeftOperand {
+                operator: kind.name().to_string(),
                 span: self.peek().span,
             }),
+            _ => Err(self.unexpected("DECLARA, SCRIBE, SCRIBE_ERRATUM, MONSTRA_TABULAM, AVTEM, or identifier")),
         }
     }
 
-    /// Parse: DECLARA <IDENT> EST <EXPR>
+    /// Parse: DECLARA <IDENT> [: <TYPE>] EST <EXPR>
     fn parse_declaration(&mut self) -> Result<Statement, NumerusError> {
         let start_span = self.advance().span; // consume DECLARA
 
         let name = self.expect_identifier()?;
+        let declared_type = if matches!(self.peek().kind, TokenKind::Colon) {
+            self.advance(); // consume ':'
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
         self.expect_token(TokenKind::Est)?;
         let value = self.parse_expression()?;
 
         Ok(Statement::Declaration {
             name,
+            declared_type,
             value: value.clone(),
             span: start_span.merge(&value.span()),
         })
     }
 
+    /// Parse a type annotation: NUMERUS | CHORDA
+    fn parse_type_annotation(&mut self) -> Result<TypeAnnotation, NumerusError> {
+        match self.peek().kind {
+            TokenKind::Numerus => {
+                self.advance();
+                Ok(TypeAnnotation::Numerus)
+            }
+            TokenKind::Chorda => {
+                self.advance();
+                Ok(TypeAnnotation::Chorda)
+            }
+            _ => Err(self.unexpected("NUMERUS or CHORDA")),
+        }
+    }
+
     /// Parse: <IDENT> EST <EXPR>
     fn parse_assignment(&mut self) -> Result<Statement, NumerusError> {
         let name_token = self.advance();
@@ -80,25 +140,76 @@ impl Parser {
         })
     }
 
-    /// Parse: SCRIBE(expression)
+    /// Parse: SCRIBE(expression) or SCRIBE(format, arg, ...)
     fn parse_print(&mut self) -> Result<Statement, NumerusError> {
         let start_span = self.advance().span; // consume SCRIBE
         self.expect_token(TokenKind::LeftParen)?;
 
         let value = self.parse_expression()?;
 
+        let mut arguments = Vec::new();
+        while matches!(self.peek().kind, TokenKind::Comma) {
+            self.advance(); // consume ','
+            arguments.push(self.parse_expression()?);
+        }
+
         let end_token = self.expect_token(TokenKind::RightParen)?;
 
         Ok(Statement::Print {
             value,
+            arguments,
             span: start_span.merge(&end_token.span),
         })
     }
 
-    /// Parse: AVTEM
+    /// Parse: SCRIBE_ERRATUM(expression)
+    fn parse_print_error(&mut self) -> Result<Statement, NumerusError> {
+        let start_span = self.advance().span; // consume SCRIBE_ERRATUM
+        self.expect_token(TokenKind::LeftParen)?;
+        let value = self.parse_expression()?;
+        let end_token = self.expect_token(TokenKind::RightParen)?;
+
+        Ok(Statement::PrintError {
+            value,
+            span: start_span.merge(&end_token.span),
+        })
+    }
+
+    /// Parse: MONSTRA_TABULAM(start, finis)
+    fn parse_table(&mut self) -> Result<Statement, NumerusError> {
+        let start_span = self.advance().span; // consume MONSTRA_TABULAM
+        self.expect_token(TokenKind::LeftParen)?;
+        let start = self.parse_expression()?;
+        self.expect_token(TokenKind::Comma)?;
+        let end = self.parse_expression()?;
+        let end_token = self.expect_token(TokenKind::RightParen)?;
+
+        Ok(Statement::Table {
+            start,
+            end,
+            span: start_span.merge(&end_token.span),
+        })
+    }
+
+    /// Parse: AVTEM or AVTEM("note")
     fn parse_avtem(&mut self) -> Result<Statement, NumerusError> {
         let token = self.advance();
-        Ok(Statement::Avtem { span: token.span })
+        if !matches!(self.peek().kind, TokenKind::LeftParen) {
+            return Ok(Statement::Avtem { message: None, span: token.span });
+        }
+
+        self.advance(); // consume '('
+        let message = match &self.peek().kind {
+            TokenKind::StringLiteral(text) => text.clone(),
+            _ => return Err(self.unexpected("string")),
+        };
+        self.advance();
+        let end = self.expect_token(TokenKind::RightParen)?;
+
+        Ok(Statement::Avtem {
+            message: Some(message),
+            span: token.span.merge(&end.span),
+        })
     }
 
     /// Parse an expression with proper operator precedence
@@ -119,6 +230,7 @@ impl Parser {
                 TokenKind::Subtrahe => BinaryOperator::Subtract,
                 _ => unreachable!(),
             };
+            self.skip_continuation_comments();
 
             let right = self.parse_multiplicative()?;
             let span = left.span().merge(&right.span());
@@ -146,6 +258,7 @@ impl Parser {
                 TokenKind::Divide => BinaryOperator::Divide,
                 _ => unreachable!(),
             };
+            self.skip_continuation_comments();
 
             let right = self.parse_factor()?;
             let span = left.span().merge(&right.span());
@@ -162,7 +275,7 @@ impl Parser {
     }
 
     /// Parse a factor (highest precedence)
-    /// factor ::= number | string | identifier | "(" expression ")" | function_call
+    /// factor ::= number | string | boolean | list | identifier | "(" expression ")" | function_call
     fn parse_factor(&mut self) -> Result<Expression, NumerusError> {
         let token = self.peek().clone();
 
@@ -186,12 +299,18 @@ impl Parser {
                 })
             }
             TokenKind::StringLiteral(s) => {
-                let value = s.clone();
+                let mut value = s.clone();
+                let mut span = token.span;
                 self.advance();
-                Ok(Expression::StringLiteral {
-                    value,
-                    span: token.span,
-                })
+
+                // Adjacent string literals ("a" "b") are joined, as in C
+                while let TokenKind::StringLiteral(next) = &self.peek().kind {
+                    value.push_str(next);
+                    span = span.merge(&self.peek().span);
+                    self.advance();
+                }
+
+                Ok(Expression::StringLiteral { value, span })
             }
             TokenKind::Identifier(_) => {
                 self.advance();
@@ -216,9 +335,47 @@ impl Parser {
             TokenKind::Romaniza => self.parse_function_call(BuiltinFunction::Romaniza),
             TokenKind::Arabiza => self.parse_function_call(BuiltinFunction::Arabiza),
             TokenKind::Exprime => self.parse_function_call(BuiltinFunction::Exprime),
+            TokenKind::Compara => self.parse_function_call(BuiltinFunction::Compara),
+            TokenKind::Primus => self.parse_function_call(BuiltinFunction::Primus),
+            TokenKind::Factores => self.parse_function_call(BuiltinFunction::Factores),
+            TokenKind::Progressio => self.parse_function_call(BuiltinFunction::Progressio),
+            TokenKind::Uncia => self.parse_function_call(BuiltinFunction::Uncia),
+            TokenKind::Sorta => self.parse_function_call(BuiltinFunction::Sorta),
+            TokenKind::Adde => self.parse_function_call(BuiltinFunction::Adde),
+            TokenKind::Remove => self.parse_function_call(BuiltinFunction::Remove),
+            TokenKind::ExAmbitu => self.parse_function_call(BuiltinFunction::ExAmbitu),
+            TokenKind::Hex => self.parse_function_call(BuiltinFunction::Hex),
+            TokenKind::Verte => self.parse_function_call(BuiltinFunction::Verte),
+            TokenKind::DivideCumResiduo => self.parse_function_call(BuiltinFunction::DivideCumResiduo),
+            TokenKind::Duplica => self.parse_function_call(BuiltinFunction::Duplica),
+            TokenKind::Dimidium => self.parse_function_call(BuiltinFunction::Dimidium),
+            TokenKind::RomanizaMinuscula => self.parse_function_call(BuiltinFunction::RomanizaMinuscula),
+            TokenKind::SummaUsque => self.parse_function_call(BuiltinFunction::SummaUsque),
+            TokenKind::SummaInter => self.parse_function_call(BuiltinFunction::SummaInter),
+            TokenKind::Inspice => self.parse_function_call(BuiltinFunction::Inspice),
+            TokenKind::Typus => self.parse_function_call(BuiltinFunction::Typus),
+            TokenKind::Par => self.parse_function_call(BuiltinFunction::Par),
+            TokenKind::Impar => self.parse_function_call(BuiltinFunction::Impar),
+            TokenKind::Verum | TokenKind::Falsum => {
+                self.advance();
+                Ok(Expression::BooleanLiteral {
+                    value: token.kind == TokenKind::Verum,
+                    span: token.span,
+                })
+            }
+            TokenKind::LeftBrace => self.parse_list(),
+            // An operator right after an opening parenthesis, EST or a comma
+            // (rather than after another operator) is missing its left side
+            kind if kind.is_operator() && !self.previous_is_operator() => {
+                Err(NumerusError::MissingLeftOperand {
+                    operator: kind.name().to_string(),
+                    span: token.span,
+                })
+            }
+            TokenKind::Eof => Err(self.unexpected("expressio")),
             _ => Err(NumerusError::ExpectedExpression {
                 after: if self.current > 0 {
-                    format!("{}", self.previous().kind)
+                    self.previous().describe()
                 } else {
                     "start".to_string()
                 },
@@ -227,17 +384,60 @@ impl Parser {
         }
     }
 
-    /// Parse a built-in function call: ROMANIZA(expr) or EXPRIME(expr)
+    /// Parse a list literal: {expr, expr, ...} (possibly empty)
+    fn parse_list(&mut self) -> Result<Expression, NumerusError> {
+        let start = self.advance().span; // consume '{'
+
+        let mut elements = Vec::new();
+        if !matches!(self.peek().kind, TokenKind::RightBrace) {
+            elements.push(self.parse_expression()?);
+            while matches!(self.peek().kind, TokenKind::Comma) {
+                self.advance(); // consume ','
+                elements.push(self.parse_expression()?);
+            }
+        }
+
+        let end = self.expect_token(TokenKind::RightBrace)?;
+        Ok(Expression::ListLiteral {
+            elements,
+            span: start.merge(&end.span),
+        })
+    }
+
+    /// Parse a built-in function call: ROMANIZA(expr) or COMPARA(expr, expr)
     fn parse_function_call(&mut self, function: BuiltinFunction) -> Result<Expression, NumerusError> {
         let start = self.advance().span;
         self.expect_token(TokenKind::LeftParen)?;
-        let argument = self.parse_expression()?;
+
+        let mut arguments = Vec::new();
+        if !matches!(self.peek().kind, TokenKind::RightParen) {
+            arguments.push(self.parse_expression()?);
+            while matches!(self.peek().kind, TokenKind::Comma) {
+                self.advance(); // consume ','
+                arguments.push(self.parse_expression()?);
+            }
+        }
+
         let end = self.expect_token(TokenKind::RightParen)?;
+        let span = start.merge(&end.span);
+
+        if arguments.len() < function.arity() || arguments.len() > function.max_arity() {
+            return Err(NumerusError::WrongArgumentCount {
+                name: function.name().to_string(),
+                expected: if arguments.len() < function.arity() {
+                    function.arity()
+                } else {
+                    function.max_arity()
+                },
+                found: arguments.len(),
+                span,
+            });
+        }
 
         Ok(Expression::FunctionCall {
             function,
-            argument: Box::new(argument),
-            span: start.merge(&end.span),
+            arguments,
+            span,
         })
     }
 
@@ -253,6 +453,18 @@ impl Parser {
         &self.tokens[self.current.saturating_sub(1)]
     }
 
+    fn previous_is_operator(&self) -> bool {
+        self.current > 0 && self.previous().kind.is_operator()
+    }
+
+    /// A line ending in an operator continues on the next line, even when a
+    /// kept comment follows the operator: `A ADDIUS NOTA: ...` then `B`
+    fn skip_continuation_comments(&mut self) {
+        while matches!(self.peek().kind, TokenKind::Comment(_)) {
+            self.advance();
+        }
+    }
+
     fn is_at_end(&self) -> bool {
         matches!(self.peek().kind, TokenKind::Eof)
     }
@@ -268,11 +480,24 @@ impl Parser {
         if std::mem::discriminant(&self.peek().kind) == std::mem::discriminant(&expected) {
             Ok(self.advance())
         } else {
-            Err(NumerusError::UnexpectedToken {
-                expected: format!("{}", expected),
-                found: format!("{}", self.peek().kind),
+            Err(self.unexpected(expected.name()))
+        }
+    }
+
+    /// Error for the current token not being `expected`; running out of
+    /// tokens is reported as the program ending too early
+    fn unexpected(&self, expected: &str) -> NumerusError {
+        if self.is_at_end() {
+            NumerusError::UnexpectedEndOfInput {
+                expected: expected.to_string(),
                 span: self.peek().span,
-            })
+            }
+        } else {
+            NumerusError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: self.peek().describe(),
+                span: self.peek().span,
+            }
         }
     }
 
@@ -283,6 +508,7 @@ impl Parser {
                 self.advance();
                 Ok(name)
             }
+            TokenKind::Eof => Err(self.unexpected("identificator")),
             _ => Err(NumerusError::ExpectedIdentifier { span: self.peek().span }),
         }
     }
@@ -291,7 +517,7 @@ impl Parser {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lexer::Lexer;
+    use crate::lexer::{Lexer, Span};
 
     fn parse(input: &str) -> Program {
         let mut lexer = Lexer::new(input);
@@ -353,6 +579,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_typed_declaration() {
+        let program = parse("DECLARA X: NUMERUS EST V\nDECLARA S: CHORDA EST \"hi\"\nDECLARA Y EST 1");
+        let types: Vec<_> = program.statements.iter().map(|s| match s {
+            Statement::Declaration { declared_type, .. } => *declared_type,
+            _ => panic!("Expected declaration"),
+        }).collect();
+        assert_eq!(types, vec![
+            Some(TypeAnnotation::Numerus),
+            Some(TypeAnnotation::Chorda),
+            None,
+        ]);
+    }
+
+    #[test]
+    fn test_parse_invalid_type_annotation() {
+        let mut lexer = Lexer::new("DECLARA X: Y EST 1");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        assert!(matches!(parser.parse(), Err(NumerusError::UnexpectedToken { .. })));
+    }
+
     #[test]
     fn test_parse_lowercase_identifier() {
         let program = parse("DECLARA myVar EST 42");
@@ -462,6 +710,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_print_with_arguments() {
+        let program = parse(r#"SCRIBE("{} et {}", X, ARABIZA(Y))"#);
+        match &program.statements[0] {
+            Statement::Print { value, arguments, .. } => {
+                assert!(matches!(value, Expression::StringLiteral { .. }));
+                assert_eq!(arguments.len(), 2);
+                assert!(matches!(arguments[0], Expression::Variable { .. }));
+                assert!(matches!(arguments[1], Expression::FunctionCall { .. }));
+            }
+            _ => panic!("Expected print"),
+        }
+    }
+
     #[test]
     fn test_parse_arabiza() {
         let program = parse(r#"SCRIBE(ARABIZA(X))"#);
@@ -478,6 +740,133 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_multi_argument_call() {
+        let program = parse("SCRIBE(COMPARA(X, II))");
+        match &program.statements[0] {
+            Statement::Print { value: Expression::FunctionCall { function, arguments, .. }, .. } => {
+                assert_eq!(*function, BuiltinFunction::Compara);
+                assert_eq!(arguments.len(), 2);
+            }
+            _ => panic!("Expected print of function call"),
+        }
+    }
+
+    #[test]
+    fn test_parse_wrong_argument_count() {
+        let mut lexer = Lexer::new("SCRIBE(COMPARA(X))");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        assert!(matches!(
+            parser.parse(),
+            Err(NumerusError::WrongArgumentCount { expected: 2, found: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_unexpected_end_of_input() {
+        let cases = [
+            ("DECLARA", "identificator"),
+            ("DECLARA X", "EST"),
+            ("DECLARA X:", "NUMERUS or CHORDA"),
+            ("DECLARA X EST", "expressio"),
+            ("DECLARA X EST II ADDIUS", "expressio"),
+            ("SCRIBE(II", ")"),
+            ("AVTEM(", "string"),
+        ];
+        for (input, expected_token) in cases {
+            let mut lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer.tokenize().unwrap());
+            match parser.parse() {
+                Err(NumerusError::UnexpectedEndOfInput { expected, span }) => {
+                    assert_eq!(expected, expected_token, "{}", input);
+                    assert_eq!(span.start, input.len(), "{}", input);
+                }
+                other => panic!("{}: expected UnexpectedEndOfInput, got {:?}", input, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_lone_est() {
+        let mut lexer = Lexer::new("EST 5");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        assert!(matches!(
+            parser.parse(),
+            Err(NumerusError::MissingVariableName { span }) if span.column == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_missing_left_operand() {
+        for input in ["ADDIUS 5", "DECLARA X EST MULTIPLICA II", "SCRIBE(DIVIDE II)"] {
+            let mut lexer = Lexer::new(input);
+            let tokens = lexer.tokenize().unwrap();
+            let mut parser = Parser::new(tokens);
+            assert!(
+                matches!(parser.parse(), Err(NumerusError::MissingLeftOperand { .. })),
+                "{}",
+                input
+            );
+        }
+
+        // A doubled operator is still reported as a missing right-hand expression
+        let mut lexer = Lexer::new("DECLARA X EST II ADDIUS ADDIUS II");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        assert!(matches!(
+            parser.parse(),
+            Err(NumerusError::ExpectedExpression { ref after, .. }) if after == "ADDIUS"
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_token_shows_lexeme() {
+        let mut lexer = Lexer::new("DECLARA X EST II\nX foo");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let err = parser.parse().unwrap_err();
+        assert!(err.to_string().contains(r#"identificator "foo""#), "{}", err);
+
+        let mut lexer = Lexer::new("DECLARA X EST II\n\"salve\" EST X");
+        let tokens = lexer.tokenize().unwrap();
+        let mut parser = Parser::new(tokens);
+        let err = parser.parse().unwrap_err();
+        assert!(err.to_string().contains(r#"string "salve""#), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_list_and_boolean_literals() {
+        let program = parse("DECLARA A EST {I, II ADDIUS III, VERUM}\nDECLARA B EST {}");
+        match &program.statements[0] {
+            Statement::Declaration { value: Expression::ListLiteral { elements, span }, .. } => {
+                assert_eq!(elements.len(), 3);
+                assert!(matches!(elements[1], Expression::BinaryOp { .. }));
+                assert!(matches!(elements[2], Expression::BooleanLiteral { value: true, .. }));
+                assert_eq!((span.start, span.end), (14, 39));
+            }
+            other => panic!("Expected list declaration, got {:?}", other),
+        }
+        assert!(matches!(
+            &program.statements[1],
+            Statement::Declaration { value: Expression::ListLiteral { elements, .. }, .. } if elements.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_optional_argument() {
+        let program = parse("SCRIBE(SORTA({II}))\nSCRIBE(SORTA({II}, VERUM))");
+        assert_eq!(program.statements.len(), 2);
+
+        let mut lexer = Lexer::new("SCRIBE(SORTA({II}, VERUM, FALSUM))");
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        assert!(matches!(
+            parser.parse(),
+            Err(NumerusError::WrongArgumentCount { expected: 2, found: 3, .. })
+        ));
+    }
+
     #[test]
     fn test_parse_string_concat() {
         let program = parse(r#"DECLARA msg EST "Hello " ADDIUS "World""#);
@@ -494,9 +883,202 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_kept_comment() {
+        let source = "DECLARA X EST XLII\n  NOTA: responsum\nSCRIBE(X)";
+        let mut lexer = Lexer::new(source);
+        lexer.set_keep_comments(true);
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        let program = parser.parse().unwrap();
+
+        assert_eq!(program.statements.len(), 3);
+        match &program.statements[1] {
+            Statement::Comment { text, span, body_span } => {
+                assert_eq!(text, "responsum");
+                assert_eq!((span.line, span.column), (2, 3));
+                assert_eq!(&source[span.start..span.end], "NOTA: responsum");
+                assert_eq!((body_span.line, body_span.column), (2, 9));
+                assert_eq!(&source[body_span.start..body_span.end], "responsum");
+            }
+            other => panic!("Expected comment, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_avtem() {
         let program = parse("AVTEM");
-        assert!(matches!(program.statements[0], Statement::Avtem { .. }));
+        assert!(matches!(program.statements[0], Statement::Avtem { message: None, .. }));
+
+        let program = parse(r#"AVTEM("ave Caesar")"#);
+        match &program.statements[0] {
+            Statement::Avtem { message: Some(message), span } => {
+                assert_eq!(message, "ave Caesar");
+                assert_eq!(span.end, 19);
+            }
+            other => panic!("Expected AVTEM with a message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_expression_entry() {
+        let mut lexer = Lexer::new("A ADDIUS B MULTIPLICA C");
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        let expression = parser.parse_expression_entry().unwrap();
+        assert!(matches!(expression, Expression::BinaryOp { operator: BinaryOperator::Add, .. }));
+
+        let mut lexer = Lexer::new("A ADDIUS B C");
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        assert!(matches!(
+            parser.parse_expression_entry(),
+            Err(NumerusError::UnexpectedToken { .. })
+        ));
+
+        // Blank lines and kept comments around the expression are not leftovers
+        let mut lexer = Lexer::new("\nNOTA: summa\n\nA ADDIUS B NOTA: finis\n\n");
+        lexer.set_keep_comments(true);
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        assert!(matches!(
+            parser.parse_expression_entry(),
+            Ok(Expression::BinaryOp { operator: BinaryOperator::Add, .. })
+        ));
+    }
+
+    #[test]
+    fn test_program_declarations() {
+        let program = parse("DECLARA X EST 1\nX EST 2\nDECLARA nomen EST \"Marcus\"");
+        let declarations: Vec<_> = program.declarations()
+            .map(|(name, span)| (name, span.line, span.column))
+            .collect();
+        assert_eq!(declarations, vec![("X", 1, 1), ("nomen", 3, 1)]);
+    }
+
+    #[test]
+    fn test_parse_empty_program() {
+        assert!(parse("").statements.is_empty());
+        assert!(parse("\n   \n").statements.is_empty());
+        assert!(parse("NOTA: solum commentarius\n").statements.is_empty());
+    }
+
+    #[test]
+    fn test_binary_op_span_covers_operands_and_operator() {
+        let source = "SCRIBE(AB SUBTRAHE CD MULTIPLICA EF)";
+        let program = parse(source);
+        let Statement::Print { value, .. } = &program.statements[0] else {
+            panic!("expected SCRIBE");
+        };
+        let Expression::BinaryOp { left, right, span, .. } = value else {
+            panic!("expected a binary operation");
+        };
+        assert_eq!(span.start, left.span().start);
+        assert_eq!(span.end, right.span().end);
+        assert_eq!(&source[span.start..span.end], "AB SUBTRAHE CD MULTIPLICA EF");
+
+        let Expression::BinaryOp { span: inner, .. } = right.as_ref() else {
+            panic!("expected a nested binary operation");
+        };
+        assert_eq!(&source[inner.start..inner.end], "CD MULTIPLICA EF");
+
+        // merge is order-independent: min start, max end
+        let (a, b) = (Span::new(7, 9, 1, 8), Span::new(19, 21, 1, 20));
+        assert_eq!(a.merge(&b), Span::new(7, 21, 1, 8));
+        assert_eq!(b.merge(&a), a.merge(&b));
+    }
+
+    #[test]
+    fn test_reset_and_reparse() {
+        let mut lexer = Lexer::new("DECLARA X EST XV ADDIUS 2\nSCRIBE(X)");
+        let mut parser = Parser::new(lexer.tokenize().unwrap());
+        let first = parser.parse().unwrap();
+        assert!(parser.position() > 0);
+
+        parser.reset();
+        assert_eq!(parser.position(), 0);
+        assert_eq!(parser.parse().unwrap(), first);
+
+        // Backtrack to the start of the second statement
+        parser.set_position(6);
+        assert_eq!(parser.parse().unwrap().statements, first.statements[1..]);
+
+        parser.set_position(1000);
+        assert!(parser.parse().unwrap().statements.is_empty());
+    }
+
+    #[test]
+    fn test_adjacent_strings_concatenate() {
+        let program = parse("DECLARA S EST \"a\" \"b\"\n    \"c\" ADDIUS X");
+        let Statement::Declaration { value, .. } = &program.statements[0] else {
+            panic!("expected a declaration");
+        };
+        let Expression::BinaryOp { left, operator: BinaryOperator::Add, .. } = value else {
+            panic!("expected ADDIUS");
+        };
+        assert!(matches!(left.as_ref(), Expression::StringLiteral { value, span } if value == "abc" && span.start == 14));
+
+        // Only string-then-string is joined; a number after a string is still an error
+        let mut lexer = Lexer::new("SCRIBE(\"a\" XV)");
+        assert!(Parser::new(lexer.tokenize().unwrap()).parse().is_err());
+    }
+
+    #[test]
+    fn test_structurally_eq_ignores_spans() {
+        let compact = parse("DECLARA X EST XV ADDIUS (II MULTIPLICA 3)\nSCRIBE(\"{}\", X)");
+        let spread = parse("\n\n   DECLARA  X  EST  XV\n  ADDIUS ( II MULTIPLICA 3 )\nSCRIBE( \"{}\" ,  X )");
+        assert_ne!(compact, spread);
+        assert!(compact.structurally_eq(&spread));
+
+        assert!(!compact.structurally_eq(&parse("DECLARA X EST XV ADDIUS (II MULTIPLICA 4)\nSCRIBE(\"{}\", X)")));
+        // The written form of a numeral is part of the structure
+        assert!(!compact.structurally_eq(&parse("DECLARA X EST 15 ADDIUS (II MULTIPLICA 3)\nSCRIBE(\"{}\", X)")));
+        assert!(!compact.structurally_eq(&parse("DECLARA X EST XV ADDIUS (II MULTIPLICA 3)")));
+    }
+
+    #[test]
+    fn test_trailing_operator_continues_line() {
+        let one_line = parse("DECLARA X EST XV ADDIUS XX MULTIPLICA II\nSCRIBE(X)");
+        for source in [
+            "DECLARA X EST XV ADDIUS\nXX MULTIPLICA II\nSCRIBE(X)",
+            "DECLARA X EST XV ADDIUS\n    XX MULTIPLICA\n    II\nSCRIBE(X)",
+            "DECLARA X EST XV ADDIUS NOTA: summa\nXX MULTIPLICA II\nSCRIBE(X)",
+        ] {
+            assert!(parse(source).structurally_eq(&one_line), "{}", source);
+
+            // Keeping comments must not break the continuation either
+            let mut lexer = Lexer::new(source);
+            lexer.set_keep_comments(true);
+            let kept = Parser::new(lexer.tokenize().unwrap()).parse().unwrap();
+            assert!(kept.structurally_eq(&one_line), "{}", source);
+        }
+
+        let split = parse("DECLARA X EST XV ADDIUS\nXX");
+        let Statement::Declaration { value: Expression::BinaryOp { span, .. }, .. } = &split.statements[0] else {
+            panic!("expected a binary declaration");
+        };
+        assert_eq!((span.line, span.column), (1, 15));
+    }
+
+    #[test]
+    fn test_builtin_functions_catalog() {
+        let catalog = crate::parser::builtin_functions();
+        assert_eq!(catalog.len(), BuiltinFunction::ALL.len());
+        for name in ["ROMANIZA", "ARABIZA", "EXPRIME"] {
+            assert!(catalog.iter().any(|(n, _)| *n == name), "{}", name);
+        }
+
+        let names: std::collections::HashSet<_> = catalog.iter().map(|(name, _)| name).collect();
+        assert_eq!(names.len(), catalog.len());
+        assert!(catalog.iter().all(|(_, description)| !description.is_empty()));
+    }
+
+    #[test]
+    fn test_display_reproduces_source() {
+        let source = "DECLARA X: NUMERUS EST XV ADDIUS (II MULTIPLICA 3)\n\
+                      X EST COMPARA(X, {VERUM, \"a\\\"b\\n\"})\n\
+                      SCRIBE(\"{}\", X)\n\
+                      MONSTRA_TABULAM(I, 5)\n\
+                      AVTEM(\"fin\")";
+        let program = parse(source);
+        let lines: Vec<String> = program.statements.iter().map(ToString::to_string).collect();
+        assert_eq!(lines.join("\n"), source);
+        assert!(parse(&lines.join("\n")).structurally_eq(&program));
     }
 }
@@ -0,0 +1,112 @@
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+
+use crate::lexer;
+
+/// REPL meta-commands, offered alongside the language keywords
+const REPL_COMMANDS: [&str; 7] = ["AUXILIUM", "EXITUS", "ARBOR", "ITERUM", "HISTORIA", "DEBUGA", "PERGE"];
+
+/// Tab-completion of keywords, built-ins and declared variable names
+pub struct NumerusCompleter {
+    keywords: Vec<String>,
+    variables: Vec<String>,
+}
+
+impl NumerusCompleter {
+    pub fn new() -> Self {
+        let keywords = lexer::keywords()
+            .iter()
+            .copied()
+            .chain(REPL_COMMANDS)
+            .map(str::to_string)
+            .collect();
+
+        Self { keywords, variables: Vec::new() }
+    }
+
+    /// Replace the snapshot of declared variable names
+    pub fn set_variables(&mut self, variables: Vec<String>) {
+        self.variables = variables;
+    }
+
+    /// Known names starting with `prefix`, sorted and without duplicates
+    pub fn candidates(&self, prefix: &str) -> Vec<String> {
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<String> = self
+            .keywords
+            .iter()
+            .chain(&self.variables)
+            .filter(|name| name.starts_with(prefix) && name.as_str() != prefix)
+            .cloned()
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+}
+
+impl Default for NumerusCompleter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Completer for NumerusCompleter {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+        Ok((start, self.candidates(&line[start..pos])))
+    }
+}
+
+impl Hinter for NumerusCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for NumerusCompleter {}
+
+impl Validator for NumerusCompleter {}
+
+impl Helper for NumerusCompleter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_candidates_for_prefix() {
+        let mut completer = NumerusCompleter::new();
+        completer.set_variables(vec!["SUMMA".to_string(), "SALVE".to_string()]);
+
+        assert_eq!(completer.candidates("SC"), vec!["SCRIBE", "SCRIBE_ERRATUM"]);
+        assert_eq!(
+            completer.candidates("S"),
+            vec!["SALVE", "SCRIBE", "SCRIBE_ERRATUM", "SORTA", "SUBTRAHE", "SUMMA", "SUMMA_INTER", "SUMMA_USQUE"]
+        );
+        assert_eq!(completer.candidates("SCRIBE"), vec!["SCRIBE_ERRATUM"]);
+        assert!(completer.candidates("SCRIBE_ERRATUM").is_empty());
+        assert!(completer.candidates("").is_empty());
+    }
+
+    #[test]
+    fn test_complete_current_word() {
+        let completer = NumerusCompleter::new();
+        let history = DefaultHistory::new();
+        let ctx = Context::new(&history);
+
+        let line = "DECLARA X EST ROM";
+        let (start, candidates) = completer.complete(line, line.len(), &ctx).unwrap();
+        assert_eq!(start, 14);
+        assert_eq!(candidates, vec!["ROMANIZA", "ROMANIZA_MINUSCULA"]);
+    }
+}
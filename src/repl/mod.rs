@@ -1,3 +1,5 @@
+mod completer;
 mod repl;
 
+pub use completer::NumerusCompleter;
 pub use repl::Repl;
@@ -1,33 +1,86 @@
+use std::collections::HashMap;
+
 use colored::*;
 use rustyline::error::ReadlineError;
-use rustyline::DefaultEditor;
+use rustyline::history::DefaultHistory;
+use rustyline::Editor;
 
+use super::NumerusCompleter;
 use crate::banner::{print_banner, print_help, print_farewell};
-use crate::error::format_error_with_context;
-use crate::interpreter::Interpreter;
+use crate::config::Config;
+use crate::error::{format_error_with_context, NumerusError};
+use crate::interpreter::{Interpreter, Value};
 use crate::lexer::Lexer;
-use crate::parser::Parser;
+use crate::parser::{Expression, NumberForm, Parser, Statement};
+use crate::roman::{to_roman, NumeralMode};
+
+/// Environment variable overriding the REPL prompt
+const PROMPT_ENV_VAR: &str = "NUMERUS_PROMPT";
+
+/// Variable holding the value of the last bare expression typed in the REPL
+const RESULT_VARIABLE: &str = "RES";
 
 /// The Numerus++ Read-Eval-Print Loop
 pub struct Repl {
     interpreter: Interpreter,
-    editor: DefaultEditor,
+    editor: Editor<NumerusCompleter, DefaultHistory>,
+    last_line: Option<String>,
+    prompt: Option<String>,
+    debugging: bool,
+    lenient: bool,
+    numeral_mode: NumeralMode,
 }
 
 impl Repl {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut editor = Editor::new()?;
+        editor.set_helper(Some(NumerusCompleter::new()));
+
+        let mut interpreter = Interpreter::new();
+        interpreter.set_on_print(Box::new(|line| println!("{}", line)));
+        interpreter.set_on_error_print(Box::new(|line| eprintln!("{}", line)));
+
         Ok(Self {
-            interpreter: Interpreter::new(),
-            editor: DefaultEditor::new()?,
+            interpreter,
+            editor,
+            last_line: None,
+            prompt: std::env::var(PROMPT_ENV_VAR).ok(),
+            debugging: false,
+            lenient: false,
+            numeral_mode: NumeralMode::Roman,
         })
     }
 
+    /// Take numeral handling and the prompt from `.numerusrc`
+    /// (a prompt set through `NUMERUS_PROMPT` is kept)
+    pub fn apply_config(&mut self, config: &Config) {
+        if self.prompt.is_none() {
+            self.prompt = config.prompt.clone();
+        }
+        self.lenient = config.lenient;
+        self.numeral_mode = config.numeral_mode;
+        self.interpreter.set_numeral_mode(config.numeral_mode);
+    }
+
+    /// Replace the default colored `NUMERUS>` prompt with a fixed string
+    pub fn set_prompt(&mut self, prompt: String) {
+        self.prompt = Some(prompt);
+    }
+
+    /// The prompt shown before each line of input
+    fn prompt(&self) -> String {
+        match &self.prompt {
+            Some(prompt) => prompt.clone(),
+            None => format!("{} ", "NUMERUS>".bright_yellow().bold()),
+        }
+    }
+
     /// Run the REPL
     pub fn run(&mut self) {
         print_banner();
 
         loop {
-            let prompt = format!("{} ", "NUMERUS>".bright_yellow().bold());
+            let prompt = self.prompt();
 
             match self.editor.readline(&prompt) {
                 Ok(line) => {
@@ -49,6 +102,33 @@ impl Repl {
                         continue;
                     }
 
+                    if let Some(code) = meta_argument(trimmed, "ARBOR") {
+                        self.show_tree(code);
+                        continue;
+                    }
+
+                    if trimmed.eq_ignore_ascii_case("ITERUM") {
+                        self.repeat_last_line();
+                        continue;
+                    }
+
+                    if trimmed.eq_ignore_ascii_case("HISTORIA") {
+                        self.show_history();
+                        continue;
+                    }
+
+                    if trimmed.eq_ignore_ascii_case("DEBUGA") {
+                        self.debugging = true;
+                        println!("{}", "Modus gradatim: sententiae singulae exsequentur. 'PERGE' pro exire.".bright_yellow());
+                        continue;
+                    }
+
+                    if trimmed.eq_ignore_ascii_case("PERGE") {
+                        self.debugging = false;
+                        println!("{}", "Modus gradatim finitus.".bright_yellow());
+                        continue;
+                    }
+
                     // Add to history
                     let _ = self.editor.add_history_entry(&line);
 
@@ -70,10 +150,60 @@ impl Repl {
         }
     }
 
+    /// ARBOR: print the syntax tree of `code`, or of the last line if empty
+    fn show_tree(&self, code: &str) {
+        let code = if code.is_empty() {
+            match &self.last_line {
+                Some(line) => line.as_str(),
+                None => {
+                    println!("{}", "Nulla linea prior. Scribe 'ARBOR <codex>'.".bright_yellow());
+                    return;
+                }
+            }
+        } else {
+            code
+        };
+
+        match syntax_tree(code, self.numeral_mode, self.lenient) {
+            Ok(tree) => println!("{}", tree.bright_cyan()),
+            Err(e) => eprintln!("{}", format_error_with_context(code, &e).bright_red()),
+        }
+    }
+
+    /// HISTORIA: list the lines entered so far (meta-commands are not recorded)
+    fn show_history(&self) {
+        let entries: Vec<&str> = self.editor.history().iter().map(String::as_str).collect();
+        if entries.is_empty() {
+            println!("{}", "Historia vacua est.".bright_yellow());
+        } else {
+            println!("{}", format_history(&entries).bright_black());
+        }
+    }
+
+    /// ITERUM: execute the last line of code again
+    fn repeat_last_line(&mut self) {
+        match self.last_line().map(str::to_string) {
+            Some(line) => {
+                println!("{}", line.bright_black());
+                self.execute_line(&line);
+            }
+            None => println!("{}", "Nulla linea prior ad iterandum.".bright_yellow()),
+        }
+    }
+
+    /// The last line of code executed (meta-commands are not recorded)
+    fn last_line(&self) -> Option<&str> {
+        self.last_line.as_deref()
+    }
+
     /// Execute a single line of Numerus++ code
     fn execute_line(&mut self, line: &str) {
+        self.last_line = Some(line.to_string());
+
         // Tokenize
         let mut lexer = Lexer::new(line);
+        lexer.set_lenient_numerals(self.lenient);
+        lexer.set_numeral_mode(self.numeral_mode);
         let tokens = match lexer.tokenize() {
             Ok(t) => t,
             Err(e) => {
@@ -82,27 +212,416 @@ impl Repl {
             }
         };
 
-        // Parse
+        // Parse as statements, falling back to a bare expression (calculator style)
         let mut parser = Parser::new(tokens);
         let program = match parser.parse() {
             Ok(p) => p,
             Err(e) => {
-                eprintln!("{}", format_error_with_context(line, &e).bright_red());
+                parser.reset();
+                match parser.parse_expression_entry() {
+                    Ok(expression) => self.evaluate_bare_expression(&expression),
+                    Err(_) => eprintln!("{}", format_error_with_context(line, &e).bright_red()),
+                }
+                self.refresh_completions();
                 return;
             }
         };
 
-        // Execute each statement
-        for statement in &program.statements {
-            if let Err(e) = self.interpreter.execute(statement) {
-                eprintln!("{}", format!("{}", e).bright_red());
+        if self.debugging {
+            self.step_through(&program.statements);
+        } else {
+            // Execute each statement
+            for statement in &program.statements {
+                if let Err(e) = self.interpreter.execute(statement) {
+                    eprintln!("{}", format!("{}", e).bright_red());
+                }
+            }
+        }
+
+        self.refresh_completions();
+    }
+
+    /// DEBUGA mode: run one statement at a time, printing what it changed and
+    /// waiting for Enter before the next
+    fn step_through(&mut self, statements: &[Statement]) {
+        let total = statements.len();
+        let stepper = Stepper::new(&mut self.interpreter, statements);
+
+        for (index, step) in (1..).zip(stepper) {
+            match step {
+                Ok(changes) if changes.is_empty() => println!("{}", "  (nihil mutatum)".bright_black()),
+                Ok(changes) => {
+                    for change in changes {
+                        println!("  {}", change.bright_cyan());
+                    }
+                }
+                Err(e) => eprintln!("{}", format!("{}", e).bright_red()),
             }
+
+            if index < total {
+                let pause = format!("[{}/{}] Preme Enter... ", index, total);
+                if self.editor.readline(&pause.bright_black().to_string()).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Print the value of a bare expression and keep it in `RES` for the next line
+    fn evaluate_bare_expression(&mut self, expression: &Expression) {
+        match self.bare_expression_output(expression) {
+            Ok(output) => println!("{}", output.bright_green()),
+            Err(e) => eprintln!("{}", format!("{}", e).bright_red()),
+        }
+    }
+
+    /// Evaluate a bare expression into `RES` and render it as SCRIBE would
+    fn bare_expression_output(&mut self, expression: &Expression) -> Result<String, NumerusError> {
+        let value = self.interpreter.evaluate(expression)?;
+        let output = self.interpreter.output_string(&value);
+        self.interpreter.declare_external(RESULT_VARIABLE, value);
+        output
+    }
+
+    /// Offer the currently declared variables for tab-completion
+    fn refresh_completions(&mut self) {
+        let names = self.interpreter.environment().variables().map(|(name, _)| name.clone()).collect();
+        if let Some(completer) = self.editor.helper_mut() {
+            completer.set_variables(names);
         }
     }
 }
 
+/// Executes statements one at a time, yielding the variables each one changed
+struct Stepper<'a> {
+    interpreter: &'a mut Interpreter,
+    statements: std::slice::Iter<'a, Statement>,
+}
+
+impl<'a> Stepper<'a> {
+    fn new(interpreter: &'a mut Interpreter, statements: &'a [Statement]) -> Self {
+        Self { interpreter, statements: statements.iter() }
+    }
+}
+
+impl Iterator for Stepper<'_> {
+    /// The environment delta as `NAME = value` lines, sorted by name
+    type Item = Result<Vec<String>, NumerusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let statement = self.statements.next()?;
+        let before = snapshot(self.interpreter);
+        if let Err(e) = self.interpreter.execute(statement) {
+            return Some(Err(e));
+        }
+
+        let mut changes: Vec<(String, Value)> = snapshot(self.interpreter)
+            .into_iter()
+            .filter(|(name, value)| before.get(name) != Some(value))
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Some(Ok(changes
+            .into_iter()
+            .map(|(name, value)| {
+                let shown = self.interpreter.output_string(&value).unwrap_or_else(|_| value.to_string());
+                format!("{} = {}", name, shown)
+            })
+            .collect()))
+    }
+}
+
+fn snapshot(interpreter: &Interpreter) -> HashMap<String, Value> {
+    interpreter
+        .environment()
+        .variables()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// If `line` is the meta-command `command` (case-insensitive), return its argument
+fn meta_argument<'a>(line: &'a str, command: &str) -> Option<&'a str> {
+    let head = line.get(..command.len())?;
+    let rest = &line[command.len()..];
+    if head.eq_ignore_ascii_case(command) && (rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
+/// Number history entries from 1, right-aligning the indices
+fn format_history(entries: &[&str]) -> String {
+    let width = entries.len().to_string().len();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| format!("{:>width$}  {}", i + 1, entry, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A node of the rendered syntax tree
+struct TreeNode {
+    label: String,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    fn leaf(label: String) -> Self {
+        Self { label, children: Vec::new() }
+    }
+}
+
+/// Render the syntax tree of a line of code: statements if it parses as a
+/// program, otherwise a bare expression; literals are read as the REPL reads them
+fn syntax_tree(code: &str, mode: NumeralMode, lenient: bool) -> Result<String, NumerusError> {
+    let mut lexer = Lexer::new(code);
+    lexer.set_lenient_numerals(lenient);
+    lexer.set_numeral_mode(mode);
+    let tokens = lexer.tokenize()?;
+
+    let nodes = match Parser::new(tokens.clone()).parse() {
+        Ok(program) => program.statements.iter().map(statement_node).collect(),
+        Err(program_error) => match Parser::new(tokens).parse_expression_entry() {
+            Ok(expression) => vec![expression_node(&expression)],
+            Err(_) => return Err(program_error),
+        },
+    };
+
+    let mut output = String::new();
+    for node in &nodes {
+        output.push_str(&node.label);
+        output.push('\n');
+        render_children(node, "", &mut output);
+    }
+    Ok(output.trim_end().to_string())
+}
+
+fn render_children(node: &TreeNode, prefix: &str, output: &mut String) {
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == node.children.len();
+        output.push_str(prefix);
+        output.push_str(if last { "└─ " } else { "├─ " });
+        output.push_str(&child.label);
+        output.push('\n');
+        let child_prefix = format!("{}{}", prefix, if last { "   " } else { "│  " });
+        render_children(child, &child_prefix, output);
+    }
+}
+
+fn statement_node(statement: &Statement) -> TreeNode {
+    match statement {
+        Statement::Declaration { name, declared_type, value, .. } => TreeNode {
+            label: match declared_type {
+                Some(annotation) => format!("DECLARA {}: {}", name, annotation.name()),
+                None => format!("DECLARA {}", name),
+            },
+            children: vec![expression_node(value)],
+        },
+        Statement::Assignment { name, value, .. } => TreeNode {
+            label: format!("{} EST", name),
+            children: vec![expression_node(value)],
+        },
+        Statement::Print { value, arguments, .. } => TreeNode {
+            label: "SCRIBE".to_string(),
+            children: std::iter::once(value).chain(arguments).map(expression_node).collect(),
+        },
+        Statement::PrintError { value, .. } => TreeNode {
+            label: "SCRIBE_ERRATUM".to_string(),
+            children: vec![expression_node(value)],
+        },
+        Statement::Table { start, end, .. } => TreeNode {
+            label: "MONSTRA_TABULAM".to_string(),
+            children: vec![expression_node(start), expression_node(end)],
+        },
+        Statement::Avtem { message: None, .. } => TreeNode::leaf("AVTEM".to_string()),
+        Statement::Avtem { message: Some(message), .. } => TreeNode::leaf(format!("AVTEM(\"{}\")", message)),
+        Statement::Comment { text, .. } => TreeNode::leaf(format!("NOTA: {}", text)),
+    }
+}
+
+fn expression_node(expression: &Expression) -> TreeNode {
+    match expression {
+        Expression::NumberLiteral { value, original_form, .. } => TreeNode::leaf(match original_form {
+            NumberForm::Roman => to_roman(*value).unwrap_or_else(|_| value.to_string()),
+            NumberForm::Arabic => value.to_string(),
+        }),
+        Expression::StringLiteral { value, .. } => TreeNode::leaf(format!("\"{}\"", value)),
+        Expression::BooleanLiteral { value, .. } => {
+            TreeNode::leaf(if *value { "VERUM" } else { "FALSUM" }.to_string())
+        }
+        Expression::ListLiteral { elements, .. } => TreeNode {
+            label: "{ }".to_string(),
+            children: elements.iter().map(expression_node).collect(),
+        },
+        Expression::Variable { name, .. } => TreeNode::leaf(name.clone()),
+        Expression::BinaryOp { left, operator, right, .. } => TreeNode {
+            label: operator.symbol().to_string(),
+            children: vec![expression_node(left), expression_node(right)],
+        },
+        Expression::Grouped { inner, .. } => TreeNode {
+            label: "( )".to_string(),
+            children: vec![expression_node(inner)],
+        },
+        Expression::FunctionCall { function, arguments, .. } => TreeNode {
+            label: function.name().to_string(),
+            children: arguments.iter().map(expression_node).collect(),
+        },
+    }
+}
+
 impl Default for Repl {
     fn default() -> Self {
         Self::new().expect("Failed to create REPL")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_prompt() {
+        let mut repl = Repl::new().unwrap();
+        repl.set_prompt("> ".to_string());
+        assert_eq!(repl.prompt(), "> ");
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let mut repl = Repl::new().unwrap();
+        repl.set_prompt("$ ".to_string());
+        repl.apply_config(&Config::parse("numerals = arabic\nprompt = \"> \"").unwrap());
+        assert_eq!(repl.prompt(), "$ ");
+
+        repl.execute_line("DECLARA XIV EST 4000");
+        assert_eq!(repl.interpreter.environment().get("XIV").unwrap(), Value::Number(4000));
+
+        // Bare expressions and DEBUGA steps print numbers the way SCRIBE does
+        let parser = |source: &str| {
+            let mut lexer = Lexer::new(source);
+            lexer.set_numeral_mode(NumeralMode::ArabicOnly);
+            Parser::new(lexer.tokenize().unwrap())
+        };
+        let expression = parser("4000 ADDIUS 1").parse_expression_entry().unwrap();
+        assert_eq!(repl.bare_expression_output(&expression).unwrap(), "4001");
+        let program = parser("XIV EST XIV ADDIUS 2").parse().unwrap();
+        let steps: Vec<_> = Stepper::new(&mut repl.interpreter, &program.statements).map(Result::unwrap).collect();
+        assert_eq!(steps, vec![vec!["XIV = 4002".to_string()]]);
+    }
+
+    #[test]
+    fn test_last_line_tracks_executed_code() {
+        let mut repl = Repl::new().unwrap();
+        assert_eq!(repl.last_line(), None);
+
+        repl.execute_line("DECLARA X EST XLII");
+        assert_eq!(repl.last_line(), Some("DECLARA X EST XLII"));
+
+        // Repeating re-runs the same line, which keeps it as the last line
+        repl.repeat_last_line();
+        assert_eq!(repl.last_line(), Some("DECLARA X EST XLII"));
+    }
+
+    #[test]
+    fn test_bare_expressions_chain_through_res() {
+        let mut repl = Repl::new().unwrap();
+        repl.execute_line("DECLARA A EST XV");
+        repl.execute_line("A ADDIUS XX");
+        assert_eq!(repl.interpreter.environment().get("RES").unwrap(), Value::Number(35));
+
+        repl.execute_line("RES MULTIPLICA II");
+        assert_eq!(repl.interpreter.environment().get("RES").unwrap(), Value::Number(70));
+
+        // A line that is neither a statement nor an expression leaves RES alone
+        repl.execute_line("RES ADDIUS");
+        assert_eq!(repl.interpreter.environment().get("RES").unwrap(), Value::Number(70));
+    }
+
+    #[test]
+    fn test_stepper_yields_after_each_statement() {
+        let tokens = Lexer::new("DECLARA A EST XV\nDECLARA B EST XX\nSCRIBE(A)\nA EST B").tokenize().unwrap();
+        let program = Parser::new(tokens).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+
+        let steps: Vec<_> = Stepper::new(&mut interpreter, &program.statements).map(Result::unwrap).collect();
+        assert_eq!(
+            steps,
+            vec![
+                vec!["A = XV".to_string()],
+                vec!["B = XX".to_string()],
+                vec![],
+                vec!["A = XX".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stepper_reports_errors_per_statement() {
+        let tokens = Lexer::new("SCRIBE(NULLUS)\nDECLARA A EST XV").tokenize().unwrap();
+        let program = Parser::new(tokens).parse().unwrap();
+        let mut interpreter = Interpreter::new();
+
+        let mut stepper = Stepper::new(&mut interpreter, &program.statements);
+        assert!(stepper.next().unwrap().is_err());
+        assert_eq!(stepper.next().unwrap().unwrap(), vec!["A = XV".to_string()]);
+        assert!(stepper.next().is_none());
+    }
+
+    #[test]
+    fn test_meta_argument() {
+        assert_eq!(meta_argument("ARBOR X EST 1", "ARBOR"), Some("X EST 1"));
+        assert_eq!(meta_argument("arbor", "ARBOR"), Some(""));
+        assert_eq!(meta_argument("ARBORES EST 1", "ARBOR"), None);
+        assert_eq!(meta_argument("X EST 1", "ARBOR"), None);
+    }
+
+    #[test]
+    fn test_format_history() {
+        assert_eq!(format_history(&[]), "");
+        assert_eq!(format_history(&["DECLARA X EST XV", "SCRIBE(X)"]), "1  DECLARA X EST XV\n2  SCRIBE(X)");
+
+        let entries = vec!["AVTEM"; 10];
+        let listing = format_history(&entries);
+        assert!(listing.starts_with(" 1  AVTEM\n"), "{}", listing);
+        assert!(listing.ends_with("\n10  AVTEM"), "{}", listing);
+    }
+
+    #[test]
+    fn test_syntax_tree_expression_precedence() {
+        let tree = syntax_tree("A ADDIUS B MULTIPLICA C", NumeralMode::Roman, false).unwrap();
+        assert_eq!(tree, "ADDIUS\n├─ A\n└─ MULTIPLICA\n   ├─ B\n   └─ C");
+    }
+
+    #[test]
+    fn test_syntax_tree_statement() {
+        let tree = syntax_tree("DECLARA X EST (XL ADDIUS 2) DIVIDE Y", NumeralMode::Roman, false).unwrap();
+        assert_eq!(
+            tree,
+            "DECLARA X\n└─ DIVIDE\n   ├─ ( )\n   │  └─ ADDIUS\n   │     ├─ XL\n   │     └─ 2\n   └─ Y"
+        );
+    }
+
+    #[test]
+    fn test_syntax_tree_follows_numeral_settings() {
+        // Not a canonical numeral, so a name unless the REPL is lenient
+        assert_eq!(
+            syntax_tree("DECLARA X EST IIII", NumeralMode::Roman, false).unwrap(),
+            "DECLARA X\n└─ IIII"
+        );
+        assert_eq!(
+            syntax_tree("DECLARA X EST IIII", NumeralMode::Roman, true).unwrap(),
+            "DECLARA X\n└─ IV"
+        );
+        assert_eq!(
+            syntax_tree("XIV ADDIUS -5000", NumeralMode::ArabicOnly, false).unwrap(),
+            "ADDIUS\n├─ XIV\n└─ -5000"
+        );
+    }
+
+    #[test]
+    fn test_syntax_tree_reports_parse_error() {
+        assert!(syntax_tree("DECLARA X EST", NumeralMode::Roman, false).is_err());
+    }
+}
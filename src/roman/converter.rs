@@ -1,5 +1,11 @@
-/// Roman numeral conversion utilities
-/// Handles bidirectional conversion between Arabic integers and Roman numeral strings
+//! Roman numeral conversion utilities
+//! Handles bidirectional conversion between Arabic integers and Roman numeral strings
+//!
+//! Builds without the `std` feature: `to_roman_into` and the success path of
+//! `from_roman` never allocate, the rest only needs `alloc`.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
 
 const ROMAN_VALUES: [(i32, &str); 13] = [
     (1000, "M"),
@@ -17,39 +23,68 @@ const ROMAN_VALUES: [(i32, &str); 13] = [
     (1, "I"),
 ];
 
+/// Largest value expressible as a Roman numeral (MMMCMXCIX)
+pub const MAX_ROMAN: i32 = 3999;
+
+/// Length of the longest Roman numeral in range (MMMDCCCLXXXVIII)
+pub const MAX_ROMAN_LEN: usize = 15;
+
 /// Convert an Arabic integer (1-3999) to a Roman numeral string
-pub fn to_roman(mut n: i32) -> Result<String, RomanError> {
+pub fn to_roman(n: i32) -> Result<String, RomanError> {
+    let mut buffer = [0u8; MAX_ROMAN_LEN];
+    to_roman_into(n, &mut buffer).map(|roman| roman.to_string())
+}
+
+/// Convert an Arabic integer (1-3999) to a lowercase Roman numeral string
+/// (`xlii`), as used for page numbers
+pub fn to_roman_lower(n: i32) -> Result<String, RomanError> {
+    to_roman(n).map(|roman| roman.to_ascii_lowercase())
+}
+
+/// Convert an Arabic integer (1-3999) to a Roman numeral, writing it into a
+/// caller-provided buffer instead of allocating
+pub fn to_roman_into(n: i32, buffer: &mut [u8]) -> Result<&str, RomanError> {
     if n <= 0 {
         return Err(RomanError::NegativeOrZero(n));
     }
-    if n > 3999 {
+    if n > MAX_ROMAN {
         return Err(RomanError::Overflow(n));
     }
 
-    let mut result = String::new();
+    let mut remaining = n;
+    let mut len = 0;
     for (value, symbol) in ROMAN_VALUES {
-        while n >= value {
-            result.push_str(symbol);
-            n -= value;
+        while remaining >= value {
+            let end = len + symbol.len();
+            if end > buffer.len() {
+                return Err(RomanError::BufferTooSmall(buffer.len()));
+            }
+            buffer[len..end].copy_from_slice(symbol.as_bytes());
+            len = end;
+            remaining -= value;
         }
     }
-    Ok(result)
+
+    // Only ASCII symbols were written
+    Ok(core::str::from_utf8(&buffer[..len]).expect("Roman numerals are ASCII"))
 }
 
 /// Convert a Roman numeral string to an Arabic integer
 /// Validates proper subtractive notation and symbol rules
+///
+/// Letters are matched ASCII case-insensitively in place, so a valid numeral
+/// is read without allocating.
 pub fn from_roman(s: &str) -> Result<i32, RomanError> {
     if s.is_empty() {
         return Err(RomanError::Empty);
     }
 
-    let s = s.to_uppercase();
     let mut total = 0i32;
     let mut prev_value = 0i32;
     let mut consecutive_count = 1;
     let mut prev_char: Option<char> = None;
 
-    for ch in s.chars().rev() {
+    for ch in s.chars().rev().map(|ch| ch.to_ascii_uppercase()) {
         let value = match ch {
             'I' => 1,
             'V' => 5,
@@ -87,7 +122,7 @@ pub fn from_roman(s: &str) -> Result<i32, RomanError> {
                 (100, 500) | (100, 1000) // CD, CM
             );
             if !valid_subtractive {
-                return Err(RomanError::InvalidSubtractive(s.clone()));
+                return Err(RomanError::InvalidSubtractive(s.to_ascii_uppercase()));
             }
             total -= value;
         } else {
@@ -99,15 +134,40 @@ pub fn from_roman(s: &str) -> Result<i32, RomanError> {
     }
 
     // Final validation: convert back and check it matches
-    if let Ok(reconverted) = to_roman(total) {
-        if reconverted != s {
-            return Err(RomanError::NonCanonical(s, reconverted));
-        }
+    // (a total outside I-MMMCMXCIX cannot be canonical either)
+    let mut buffer = [0u8; MAX_ROMAN_LEN];
+    let reconverted = to_roman_into(total, &mut buffer)?;
+    if !reconverted.eq_ignore_ascii_case(s) {
+        return Err(RomanError::NonCanonical(s.to_ascii_uppercase(), reconverted.to_string()));
     }
 
     Ok(total)
 }
 
+/// Additive "clock-face" spellings accepted by lenient parsing, longest first,
+/// with the subtractive form each one stands for
+const ADDITIVE_FORMS: [(&str, &str); 6] = [
+    ("DCCCC", "CM"),
+    ("CCCC", "CD"),
+    ("LXXXX", "XC"),
+    ("XXXX", "XL"),
+    ("VIIII", "IX"),
+    ("IIII", "IV"),
+];
+
+/// Convert a Roman numeral string to an Arabic integer, additionally accepting
+/// additive spellings such as `IIII` (clock-face style) or `XXXX`
+///
+/// Other malformed numerals are still rejected with the same error `from_roman` gives.
+pub fn from_roman_lenient(s: &str) -> Result<i32, RomanError> {
+    let mut normalized = s.to_uppercase();
+    for (additive, subtractive) in ADDITIVE_FORMS {
+        normalized = normalized.replace(additive, subtractive);
+    }
+
+    from_roman(&normalized).or_else(|_| from_roman(s))
+}
+
 /// Check if a string looks like it could be a Roman numeral
 pub fn looks_like_roman(s: &str) -> bool {
     !s.is_empty() && s.chars().all(|c| matches!(c, 'I' | 'V' | 'X' | 'L' | 'C' | 'D' | 'M'))
@@ -123,16 +183,17 @@ pub enum RomanError {
     TooManyRepetitions(char),
     InvalidSubtractive(String),
     NonCanonical(String, String),
+    BufferTooSmall(usize),
 }
 
-impl std::fmt::Display for RomanError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for RomanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             RomanError::NegativeOrZero(n) => {
                 write!(f, "ERRATUM: Numerus {} negativus vel nihil est! Romani non cognoverunt.", n)
             }
             RomanError::Overflow(n) => {
-                write!(f, "ERRATUM: Numerus {} nimis magnus pro Romanis (maximum MMMCMXCIX)!", n)
+                write!(f, "ERRATUM: Numerus {} extra fines est! (1–3999 / I–MMMCMXCIX)", n)
             }
             RomanError::Empty => {
                 write!(f, "ERRATUM: Numerus Romanus vacuus est!")
@@ -152,11 +213,14 @@ impl std::fmt::Display for RomanError {
             RomanError::NonCanonical(got, expected) => {
                 write!(f, "ERRATUM: '{}' non est forma canonica! Expectabatur '{}'.", got, expected)
             }
+            RomanError::BufferTooSmall(capacity) => {
+                write!(f, "ERRATUM: Spatium {} octetorum pro numero Romano non sufficit!", capacity)
+            }
         }
     }
 }
 
-impl std::error::Error for RomanError {}
+impl core::error::Error for RomanError {}
 
 #[cfg(test)]
 mod tests {
@@ -194,6 +258,13 @@ mod tests {
         assert_eq!(to_roman(3999).unwrap(), "MMMCMXCIX");
     }
 
+    #[test]
+    fn test_to_roman_lower() {
+        assert_eq!(to_roman_lower(42).unwrap(), "xlii");
+        assert_eq!(to_roman_lower(3999).unwrap(), "mmmcmxcix");
+        assert_eq!(to_roman_lower(0), Err(RomanError::NegativeOrZero(0)));
+    }
+
     #[test]
     fn test_to_roman_boundaries() {
         assert!(to_roman(0).is_err());
@@ -201,6 +272,21 @@ mod tests {
         assert!(to_roman(4000).is_err());
     }
 
+    #[test]
+    fn test_to_roman_into_buffer() {
+        let mut buffer = [0u8; MAX_ROMAN_LEN];
+        assert_eq!(to_roman_into(1999, &mut buffer).unwrap(), "MCMXCIX");
+        assert_eq!(to_roman_into(3888, &mut buffer).unwrap(), "MMMDCCCLXXXVIII");
+        assert_eq!(to_roman_into(0, &mut buffer), Err(RomanError::NegativeOrZero(0)));
+    }
+
+    #[test]
+    fn test_to_roman_into_buffer_too_small() {
+        let mut buffer = [0u8; 3];
+        assert_eq!(to_roman_into(3, &mut buffer).unwrap(), "III");
+        assert_eq!(to_roman_into(8, &mut buffer), Err(RomanError::BufferTooSmall(3)));
+    }
+
     #[test]
     fn test_from_roman_basic() {
         assert_eq!(from_roman("I").unwrap(), 1);
@@ -241,6 +327,20 @@ mod tests {
         assert!(from_roman("ABC").is_err());   // Invalid chars
     }
 
+    #[test]
+    fn test_from_roman_ignores_ascii_case() {
+        assert_eq!(from_roman("xlii").unwrap(), 42);
+        assert_eq!(from_roman("MmXxIv").unwrap(), 2024);
+        assert_eq!(from_roman("vv"), Err(RomanError::InvalidRepetition('V')));
+        assert_eq!(from_roman("ic"), Err(RomanError::InvalidSubtractive("IC".to_string())));
+        assert_eq!(
+            from_roman("xlx"),
+            Err(RomanError::NonCanonical("XLX".to_string(), "L".to_string()))
+        );
+        // Only ASCII letters are folded: the Turkish dotless i is not an I
+        assert_eq!(from_roman("\u{131}v"), Err(RomanError::InvalidCharacter('\u{131}')));
+    }
+
     #[test]
     fn test_roundtrip() {
         for n in 1..=3999 {
@@ -250,6 +350,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_roman_repeated_subtractive_pairs() {
+        assert!(from_roman("IVIV").is_err());
+        assert!(from_roman("IXIX").is_err());
+        assert!(from_roman("XCXC").is_err());
+    }
+
+    #[test]
+    fn test_from_roman_rejects_out_of_range_total() {
+        assert_eq!(from_roman("MMMCMM"), Err(RomanError::Overflow(4900)));
+    }
+
+    #[test]
+    fn test_from_roman_fuzz_roundtrip() {
+        // Hand-rolled xorshift generator so the test stays deterministic
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let symbols = ['I', 'V', 'X', 'L', 'C', 'D', 'M'];
+        for _ in 0..100_000 {
+            let len = (next() % 10) as usize + 1;
+            let candidate: String = (0..len)
+                .map(|_| symbols[(next() % symbols.len() as u64) as usize])
+                .collect();
+
+            if let Ok(value) = from_roman(&candidate) {
+                assert_eq!(
+                    to_roman(value).unwrap(),
+                    candidate,
+                    "from_roman accepted non-canonical '{}' as {}",
+                    candidate,
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_roman_lenient() {
+        assert_eq!(from_roman_lenient("IIII").unwrap(), 4);
+        assert_eq!(from_roman_lenient("XXXX").unwrap(), 40);
+        assert_eq!(from_roman_lenient("MDCCCCLXXXXVIIII").unwrap(), 1999);
+        assert_eq!(from_roman_lenient("XLII").unwrap(), 42);
+        assert!(from_roman_lenient("IIIII").is_err());
+        assert!(from_roman_lenient("VX").is_err());
+    }
+
     #[test]
     fn test_looks_like_roman() {
         assert!(looks_like_roman("XIV"));
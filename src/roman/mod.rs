@@ -1,3 +1,5 @@
 mod converter;
+mod mode;
 
-pub use converter::{from_roman, to_roman, looks_like_roman, RomanError};
+pub use converter::{from_roman, from_roman_lenient, to_roman, to_roman_into, to_roman_lower, looks_like_roman, RomanError, MAX_ROMAN, MAX_ROMAN_LEN};
+pub use mode::NumeralMode;
@@ -0,0 +1,10 @@
+/// How number literals are read and how numbers are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumeralMode {
+    /// Roman and Arabic literals up to MMMCMXCIX; numbers print as Roman numerals
+    #[default]
+    Roman,
+    /// Ordinary i32 arithmetic: Roman literals are disabled (`XIV` is a name),
+    /// any i32 literal is accepted and numbers print in Arabic digits
+    ArabicOnly,
+}